@@ -0,0 +1,111 @@
+use crate::nalgebra::Vector2;
+use crate::*;
+
+// ASSET PATHS:
+// all optional, whatever is missing is drawn with the primitive shapes instead.
+const CAR_BODY_ATLAS_PATH: &str = "assets/car_bodies.png"; // one row per team colour
+const WHEEL_PATH: &str = "assets/wheel.png";
+const BALL_PATH: &str = "assets/ball.png";
+const ARENA_TILE_PATH: &str = "assets/arena_tile.png";
+
+pub const CAR_BODY_ATLAS_ROWS: usize = 2;
+pub const ARENA_TILE_SIZE: f32 = 4.0; // in m
+
+pub struct Assets {
+    pub car_bodies: Option<Texture2D>,
+    pub wheel: Option<Texture2D>,
+    pub ball: Option<Texture2D>,
+    pub arena_tile: Option<Texture2D>,
+}
+
+impl Assets {
+    pub async fn load() -> Assets {
+        let mut missing = Vec::new();
+        let assets = Assets {
+            car_bodies: try_load_texture(CAR_BODY_ATLAS_PATH, &mut missing).await,
+            wheel: try_load_texture(WHEEL_PATH, &mut missing).await,
+            ball: try_load_texture(BALL_PATH, &mut missing).await,
+            arena_tile: try_load_texture(ARENA_TILE_PATH, &mut missing).await,
+        };
+        if !missing.is_empty() {
+            warn!("assets not found, using primitives: {}", missing.join(", "));
+        }
+        assets
+    }
+
+    pub fn car_body_frame(&self, row: usize) -> Option<(Texture2D, Rect)> {
+        self.car_bodies.map(|atlas| {
+            let frame_h = atlas.height() / CAR_BODY_ATLAS_ROWS as f32;
+            let row = row.min(CAR_BODY_ATLAS_ROWS - 1) as f32;
+            (atlas, Rect::new(0.0, row * frame_h, atlas.width(), frame_h))
+        })
+    }
+}
+
+async fn try_load_texture<'a>(path: &'a str, missing: &mut Vec<&'a str>) -> Option<Texture2D> {
+    match load_texture(path).await {
+        Ok(texture) => {
+            texture.set_filter(FilterMode::Linear);
+            Some(texture)
+        }
+        Err(_) => {
+            missing.push(path);
+            None
+        }
+    }
+}
+
+// draws a texture (or part of it) centered on a position in m, rotated around its center
+pub fn draw_sprite_center(
+    texture: Texture2D,
+    source: Option<Rect>,
+    center: Vector2<f32>,
+    size: Vector2<f32>,
+    rotation: f32,
+    flip_x: bool,
+) {
+    let size_pxl = vec2(size_mtr_to_pxl(size.x), size_mtr_to_pxl(size.y));
+    draw_texture_ex(
+        texture,
+        pos_x_mtr_to_pxl(center.x) - size_pxl.x / 2.0,
+        pos_y_mtr_to_pxl(center.y) - size_pxl.y / 2.0,
+        WHITE,
+        DrawTextureParams {
+            dest_size: Some(size_pxl),
+            source,
+            rotation,
+            flip_x,
+            ..Default::default()
+        },
+    );
+}
+
+// fills an axis aligned area in m with a repeated texture, cutting the last row and column
+pub fn draw_tiled(texture: Texture2D, corner: Vector2<f32>, size: Vector2<f32>, tile: f32) {
+    let mut y = 0.0;
+    while y < size.y {
+        let tile_h = tile.min(size.y - y);
+        let mut x = 0.0;
+        while x < size.x {
+            let tile_w = tile.min(size.x - x);
+            draw_texture_ex(
+                texture,
+                pos_x_mtr_to_pxl(corner.x + x),
+                pos_y_mtr_to_pxl(corner.y + y),
+                WHITE,
+                DrawTextureParams {
+                    dest_size: Some(vec2(size_mtr_to_pxl(tile_w), size_mtr_to_pxl(tile_h))),
+                    source: Some(Rect::new(
+                        0.0,
+                        0.0,
+                        texture.width() * tile_w / tile,
+                        texture.height() * tile_h / tile,
+                    )),
+                    ..Default::default()
+                },
+            );
+            x += tile;
+        }
+        y += tile;
+    }
+}
@@ -0,0 +1,295 @@
+use crate::*;
+use macroquad::audio::{
+    load_sound, play_sound, set_sound_volume, stop_sound, PlaySoundParams, Sound,
+};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// AUDIO:
+// sound effects driven by the game events. all samples are optional, with none of them found (or
+// for headless runs) the manager sits on a backend that does nothing.
+// macroquad can't change the pitch of a playing sound, so the engine is three looped samples
+// recorded at rising revs, crossfaded by the wheel angular velocity.
+// quad-snd opens the sound device on a thread of its own and panics there when it can't (no ALSA
+// device on a headless linux box, for one). that panic is caught and sends the manager silent too,
+// as long as the hook from main is in place before the audio thread gets that far.
+const SOUND_DIR: &str = "assets/sounds";
+const ENGINE_BAND_SPACING: f32 = 10.0; // in rad/s of wheel spin between the engine samples
+const ENGINE_VOLUME: f32 = 0.4;
+const BALL_HIT_FULL_IMPULSE: f32 = 300.0; // impulse that plays a ball hit at full volume
+const CAR_HIT_FULL_IMPULSE: f32 = 2000.0;
+const MIN_HIT_VOLUME: f32 = 0.1;
+
+static DEVICE_FAILED: AtomicBool = AtomicBool::new(false);
+
+// call first thing in main. only quad-snd panics off the main thread are taken for a missing
+// device, everything else (an undecodable sample, for one) goes on to the default hook
+pub fn watch_audio_device() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let in_quad_snd = info
+            .location()
+            .is_some_and(|location| location.file().contains("quad-snd"));
+        let on_main_thread = std::thread::current().name() == Some("main");
+        if in_quad_snd && !on_main_thread {
+            DEVICE_FAILED.store(true, Ordering::Relaxed);
+            warn!("no audio device, playing without sound: {}", info);
+        } else {
+            default_hook(info);
+        }
+    }));
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum SoundEffect {
+    EngineIdle,
+    EngineMid,
+    EngineHigh,
+    Boost,
+    BallHit,
+    Jump,
+    Land,
+    CarHit,
+    Demolition,
+    GoalHorn,
+    Crowd,
+}
+
+impl SoundEffect {
+    const ALL: [SoundEffect; 11] = [
+        SoundEffect::EngineIdle,
+        SoundEffect::EngineMid,
+        SoundEffect::EngineHigh,
+        SoundEffect::Boost,
+        SoundEffect::BallHit,
+        SoundEffect::Jump,
+        SoundEffect::Land,
+        SoundEffect::CarHit,
+        SoundEffect::Demolition,
+        SoundEffect::GoalHorn,
+        SoundEffect::Crowd,
+    ];
+
+    const ENGINE_BANDS: [SoundEffect; 3] = [
+        SoundEffect::EngineIdle,
+        SoundEffect::EngineMid,
+        SoundEffect::EngineHigh,
+    ];
+
+    fn file_name(self) -> &'static str {
+        match self {
+            SoundEffect::EngineIdle => "engine_idle.wav",
+            SoundEffect::EngineMid => "engine_mid.wav",
+            SoundEffect::EngineHigh => "engine_high.wav",
+            SoundEffect::Boost => "boost.wav",
+            SoundEffect::BallHit => "ball_hit.wav",
+            SoundEffect::Jump => "jump.wav",
+            SoundEffect::Land => "land.wav",
+            SoundEffect::CarHit => "car_hit.wav",
+            SoundEffect::Demolition => "demolition.wav",
+            SoundEffect::GoalHorn => "goal_horn.wav",
+            SoundEffect::Crowd => "crowd.wav",
+        }
+    }
+}
+
+pub trait AudioBackend {
+    fn play(&mut self, effect: SoundEffect, volume: f32);
+    // starts a loop if it isn't running yet, otherwise only changes its volume
+    fn play_looped(&mut self, effect: SoundEffect, volume: f32);
+    // stops a loop, does nothing if it isn't running
+    fn stop(&mut self, effect: SoundEffect);
+}
+
+pub struct NoAudio;
+
+impl AudioBackend for NoAudio {
+    fn play(&mut self, _effect: SoundEffect, _volume: f32) {}
+    fn play_looped(&mut self, _effect: SoundEffect, _volume: f32) {}
+    fn stop(&mut self, _effect: SoundEffect) {}
+}
+
+pub struct MacroquadAudio {
+    sounds: HashMap<SoundEffect, Sound>, // only the ones that loaded
+    looping: HashSet<SoundEffect>,
+}
+
+impl MacroquadAudio {
+    pub async fn load() -> Option<MacroquadAudio> {
+        if DEVICE_FAILED.load(Ordering::Relaxed) {
+            return None;
+        }
+        let mut sounds = HashMap::new();
+        let mut missing = Vec::new();
+        for effect in SoundEffect::ALL {
+            let path = format!("{}/{}", SOUND_DIR, effect.file_name());
+            match load_sound(&path).await {
+                Ok(sound) => {
+                    sounds.insert(effect, sound);
+                }
+                Err(_) => missing.push(path),
+            }
+        }
+        if !missing.is_empty() {
+            warn!(
+                "sounds not found, playing without them: {}",
+                missing.join(", ")
+            );
+        }
+        if sounds.is_empty() {
+            return None;
+        }
+        Some(MacroquadAudio {
+            sounds,
+            looping: HashSet::new(),
+        })
+    }
+}
+
+impl AudioBackend for MacroquadAudio {
+    fn play(&mut self, effect: SoundEffect, volume: f32) {
+        if let Some(sound) = self.sounds.get(&effect) {
+            play_sound(
+                *sound,
+                PlaySoundParams {
+                    looped: false,
+                    volume,
+                },
+            );
+        }
+    }
+
+    fn play_looped(&mut self, effect: SoundEffect, volume: f32) {
+        if let Some(sound) = self.sounds.get(&effect) {
+            if self.looping.insert(effect) {
+                play_sound(
+                    *sound,
+                    PlaySoundParams {
+                        looped: true,
+                        volume,
+                    },
+                );
+            } else {
+                set_sound_volume(*sound, volume);
+            }
+        }
+    }
+
+    fn stop(&mut self, effect: SoundEffect) {
+        if !self.looping.remove(&effect) {
+            return;
+        }
+        if let Some(sound) = self.sounds.get(&effect) {
+            stop_sound(*sound);
+        }
+    }
+}
+
+pub struct AudioManager {
+    backend: Box<dyn AudioBackend>,
+    volume: f32, // master times effects volume
+}
+
+impl AudioManager {
+    pub async fn load() -> AudioManager {
+        match MacroquadAudio::load().await {
+            Some(backend) => AudioManager::with_backend(Box::new(backend)),
+            None => AudioManager::silent(),
+        }
+    }
+
+    pub fn silent() -> AudioManager {
+        AudioManager::with_backend(Box::new(NoAudio))
+    }
+
+    pub fn with_backend(backend: Box<dyn AudioBackend>) -> AudioManager {
+        AudioManager {
+            backend,
+            volume: 1.0,
+        }
+    }
+
+    pub fn apply_settings(&mut self, settings: &Settings) {
+        self.volume = settings.master_volume * settings.effects_volume;
+    }
+
+    // the device can fail after loading too, whenever the audio thread gets to open it
+    fn backend(&mut self) -> &mut dyn AudioBackend {
+        if DEVICE_FAILED.load(Ordering::Relaxed) {
+            self.backend = Box::new(NoAudio);
+        }
+        self.backend.as_mut()
+    }
+
+    fn play(&mut self, effect: SoundEffect, volume: f32) {
+        let volume = volume * self.volume;
+        self.backend().play(effect, volume);
+    }
+
+    // crossfades the engine samples, each one is loudest at its band and silent one band away
+    pub fn update_engine(&mut self, wheel_angvel: f32) {
+        let revs = wheel_angvel.abs() / ENGINE_BAND_SPACING;
+        for (band, effect) in SoundEffect::ENGINE_BANDS.iter().enumerate() {
+            let last = band == SoundEffect::ENGINE_BANDS.len() - 1;
+            let weight = if last && revs >= band as f32 {
+                1.0
+            } else {
+                (1.0 - (revs - band as f32).abs()).max(0.0)
+            };
+            let volume = weight * ENGINE_VOLUME * self.volume;
+            self.backend().play_looped(*effect, volume);
+        }
+    }
+
+    pub fn set_boosting(&mut self, boosting: bool) {
+        let volume = self.volume;
+        if boosting {
+            self.backend().play_looped(SoundEffect::Boost, volume);
+        } else {
+            self.backend().stop(SoundEffect::Boost);
+        }
+    }
+
+    pub fn jump(&mut self) {
+        self.play(SoundEffect::Jump, 1.0);
+    }
+
+    // stops all loops, when leaving a match or pausing
+    pub fn silence(&mut self) {
+        for effect in SoundEffect::ENGINE_BANDS {
+            self.backend().stop(effect);
+        }
+        self.backend().stop(SoundEffect::Boost);
+    }
+}
+
+fn hit_volume(impulse: f32, full_impulse: f32) -> f32 {
+    (impulse / full_impulse).clamp(MIN_HIT_VOLUME, 1.0)
+}
+
+impl GameEventSubscriber for AudioManager {
+    fn on_event(&mut self, event: &GameEvent) {
+        match *event {
+            GameEvent::BallTouched { impulse, .. } | GameEvent::BallHitWall { impulse, .. } => self
+                .play(
+                    SoundEffect::BallHit,
+                    hit_volume(impulse, BALL_HIT_FULL_IMPULSE),
+                ),
+            GameEvent::CarLanded { .. } => self.play(SoundEffect::Land, 0.6),
+            GameEvent::CarHitWall { impulse, .. } | GameEvent::CarsCollided { impulse, .. } => self
+                .play(
+                    SoundEffect::CarHit,
+                    hit_volume(impulse, CAR_HIT_FULL_IMPULSE),
+                ),
+            GameEvent::BumperHit { .. } => self.play(SoundEffect::CarHit, 0.8),
+            GameEvent::Demolition { .. } | GameEvent::KnockedOut { .. } => {
+                self.play(SoundEffect::Demolition, 1.0)
+            }
+            GameEvent::GoalScored { .. } | GameEvent::RoundOver { .. } => {
+                self.play(SoundEffect::GoalHorn, 1.0);
+                self.play(SoundEffect::Crowd, 0.8);
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use crate::car::Car;
+use crate::*;
+
+// CAR COLLISIONS:
+// when two car bodies meet, the car driving into the other one is the attacker. it pushes the victim
+// away, or demolishes it when hitting an opponent at supersonic speed. demolished cars respawn on
+// their own half after the respawn time.
+const BUMP_LIFT: f32 = 0.3; // upward share of the bump direction
+
+// returns the demolitions caused by the car collisions among the events
+pub fn resolve_car_collisions(
+    events: &[GameEvent],
+    cars: &mut [Car],
+    respawn_time: f32,
+    body_set: &mut RigidBodySet,
+    coll_set: &mut ColliderSet,
+) -> Vec<GameEvent> {
+    let mut demolitions = Vec::new();
+
+    for event in events.iter() {
+        let (car1, car2) = match *event {
+            GameEvent::CarsCollided { car1, car2, .. } => (car1, car2),
+            _ => continue,
+        };
+        let (index1, index2) = match (
+            cars.iter().position(|car| car.id == car1),
+            cars.iter().position(|car| car.id == car2),
+        ) {
+            (Some(index1), Some(index2)) => (index1, index2),
+            _ => continue,
+        };
+        if cars[index1].is_demolished() || cars[index2].is_demolished() {
+            continue;
+        }
+
+        // speed of each car towards the other one:
+        let towards = (cars[index2].get_position(body_set) - cars[index1].get_position(body_set))
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_else(|| vector![1.0, 0.0]);
+        let velocity1 = cars[index1].get_velocity(body_set);
+        let velocity2 = cars[index2].get_velocity(body_set);
+        let approach1 = velocity1.dot(&towards);
+        let approach2 = -velocity2.dot(&towards);
+        if approach1 <= 0.0 && approach2 <= 0.0 {
+            continue;
+        }
+        let (attacker, victim, attacker_velocity) = if approach1 >= approach2 {
+            (index1, index2, velocity1)
+        } else {
+            (index2, index1, velocity2)
+        };
+
+        if cars[attacker].side != cars[victim].side && cars[attacker].is_supersonic(body_set) {
+            cars[victim].demolish(respawn_time, body_set, coll_set);
+            demolitions.push(GameEvent::Demolition {
+                attacker: cars[attacker].id,
+                victim: cars[victim].id,
+            });
+            continue;
+        }
+
+        let closing_speed = approach1 + approach2;
+        let direction = (attacker_velocity.normalize() + vector![0.0, -BUMP_LIFT]).normalize();
+        body_set[cars[victim].body_handle()]
+            .apply_impulse(direction * closing_speed.max(0.0) * BUMP_STRENGTH, true);
+    }
+
+    demolitions
+}
+
+// counts down the demolished cars and brings them back when due
+pub fn update_respawns(
+    cars: &mut [Car],
+    dt: f32,
+    body_set: &mut RigidBodySet,
+    coll_set: &mut ColliderSet,
+) {
+    for car in cars.iter_mut() {
+        if car.tick_respawn(dt) {
+            car.respawn(respawn_point(car.side), body_set, coll_set);
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::nalgebra::Vector2;
+use crate::*;
+
+// CAMERA:
+// follows the player car, or in ball cam keeps car and ball in frame.
+// works on top of the arena pixel space from utils, so objects keep drawing with the usual helpers.
+const FOLLOW_ZOOM: f32 = 1.8;
+const MIN_ZOOM: f32 = 1.0; // full arena view
+const BALL_CAM_MARGIN: f32 = 12.0; // in m, kept around car and ball
+const PAN_SMOOTHING: f32 = 4.0; // higher = snappier
+const ZOOM_SMOOTHING: f32 = 2.5;
+const SHAKE_DECAY: f32 = 1.5; // trauma per second
+const SHAKE_MAX_OFFSET: f32 = 1.2; // in m
+const SHAKE_FREQUENCY: f32 = 25.0;
+const BIG_HIT_DELTA_V: f32 = 15.0; // in m/s, ball velocity change that counts as a big hit
+
+// height of the arena pixel space in m, the viewport is taller than the arena itself
+fn view_height() -> f32 {
+    ARENA_WIDTH / ASPECT_RATIO
+}
+
+pub struct GameCamera {
+    pub ball_cam: bool,
+    target: Vector2<f32>, // in m
+    zoom: f32,
+    trauma: f32,
+    time: f32,
+}
+
+impl GameCamera {
+    pub fn new(target: Vector2<f32>) -> GameCamera {
+        GameCamera {
+            ball_cam: false,
+            target,
+            zoom: FOLLOW_ZOOM,
+            trauma: 0.0,
+            time: 0.0,
+        }
+    }
+
+    pub fn toggle_ball_cam(&mut self) {
+        self.ball_cam = !self.ball_cam;
+    }
+
+    // trauma in 0..1, shake strength grows with the square of it
+    pub fn shake(&mut self, trauma: f32) {
+        self.trauma = (self.trauma + trauma).min(1.0);
+    }
+
+    pub fn update(&mut self, car_pos: Vector2<f32>, ball_pos: Vector2<f32>, dt: f32) {
+        let (desired_target, desired_zoom) = if self.ball_cam {
+            let span = (car_pos - ball_pos).abs() + vector![BALL_CAM_MARGIN, BALL_CAM_MARGIN] * 2.0;
+            let zoom = (ARENA_WIDTH / span.x)
+                .min(view_height() / span.y)
+                .clamp(MIN_ZOOM, FOLLOW_ZOOM);
+            ((car_pos + ball_pos) / 2.0, zoom)
+        } else {
+            (car_pos, FOLLOW_ZOOM)
+        };
+
+        // exponential smoothing, framerate independent:
+        let pan_t = 1.0 - (-PAN_SMOOTHING * dt).exp();
+        let zoom_t = 1.0 - (-ZOOM_SMOOTHING * dt).exp();
+        self.target += (desired_target - self.target) * pan_t;
+        self.zoom += (desired_zoom - self.zoom) * zoom_t;
+
+        self.trauma = (self.trauma - SHAKE_DECAY * dt).max(0.0);
+        self.time += dt;
+    }
+
+    pub fn on_ball_hit(&mut self, delta_v: f32) {
+        if delta_v > BIG_HIT_DELTA_V {
+            self.shake((delta_v / BIG_HIT_DELTA_V - 1.0).min(1.0) * 0.6 + 0.2);
+        }
+    }
+
+    // keeps the visible area inside the arena, centered on an axis where the arena fits entirely
+    fn clamped_target(&self) -> Vector2<f32> {
+        let clamp_axis = |target: f32, visible: f32, arena: f32| {
+            if visible >= arena {
+                arena / 2.0
+            } else {
+                target.clamp(visible / 2.0, arena - visible / 2.0)
+            }
+        };
+        vector![
+            clamp_axis(self.target.x, ARENA_WIDTH / self.zoom, ARENA_WIDTH),
+            clamp_axis(self.target.y, view_height() / self.zoom, ARENA_HEIGHT)
+        ]
+    }
+
+    fn shake_offset(&self) -> Vector2<f32> {
+        let strength = self.trauma * self.trauma * SHAKE_MAX_OFFSET;
+        let t = self.time * SHAKE_FREQUENCY;
+        vector![
+            (t * 1.3).sin() + (t * 2.9).sin() * 0.5,
+            (t * 1.7).cos() + (t * 3.1).cos() * 0.5
+        ] * strength
+            / 1.5
+    }
+
+    // where a point of the arena ends up on screen with this camera, shake left out
+    pub fn world_to_screen(&self, position: Vector2<f32>) -> Vec2 {
+        let target = pos_vec_mtr_to_pxl(self.clamped_target());
+        let point = pos_vec_mtr_to_pxl(position);
+        vec2(
+            screen_width() / 2.0 + (point.x - target.x) * self.zoom,
+            screen_height() / 2.0 + (point.y - target.y) * self.zoom,
+        )
+    }
+
+    pub fn set_active(&self) {
+        let target = pos_vec_mtr_to_pxl(self.clamped_target() + self.shake_offset());
+        set_camera(&Camera2D {
+            target: vec2(target.x, target.y),
+            zoom: vec2(
+                2.0 * self.zoom / screen_width(),
+                -2.0 * self.zoom / screen_height(),
+            ),
+            ..Default::default()
+        });
+    }
+}
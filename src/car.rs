@@ -1,28 +1,26 @@
 use crate::nalgebra::Vector2;
 use crate::*;
 
-const HEIGHT: f32 = 1.6;
-const LENGTH: f32 = 10.0;
 const WHEEL_RADIUS: f32 = 1.8;
 const WHEEL_FRONT_X_OFFSET: f32 = 3.4;
 const WHEEL_FRONT_Y_OFFSET: f32 = 0.0;
 const WHEEL_BACK_X_OFFSET: f32 = -3.4;
 const WHEEL_BACK_Y_OFFSET: f32 = 0.0;
+const AIR_DERIVATIVE_GAIN: f32 = 0.02; // in s, D term of the air control
 
 #[derive(Debug)]
 enum CarPart {
     Body {
         body_handle: RigidBodyHandle,
         coll_handle: ColliderHandle,
-        joint_handle: Option<JointHandle>,
     },
     Wheel {
         body_handle: RigidBodyHandle,
         coll_handle: ColliderHandle,
-        joint_handle: Option<JointHandle>,
     },
 }
-enum SelectPart {
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SelectPart {
     Body,
     Wheel,
 }
@@ -31,26 +29,29 @@ impl CarPart {
     fn new(
         shape: SelectPart,
         position: Vector2<f32>,
-        half_extents: Vector2<f32>,
+        collider_shape: SharedShape,
+        team: CollisionLayer,
         body_set: &mut RigidBodySet,
         coll_set: &mut ColliderSet,
     ) -> CarPart {
         let body = RigidBodyBuilder::new_dynamic()
             .translation(position)
-            .additional_mass(50.0)
-            .linear_damping(0.5)
-            .angular_damping(5.0)
+            .additional_mass(CAR_ADDITIONAL_MASS)
+            .linear_damping(CAR_LINEAR_DAMPING)
+            .angular_damping(CAR_ANGULAR_DAMPING)
             .build();
         let body_handle = body_set.insert(body);
 
         let collider = match shape {
-            SelectPart::Body => ColliderBuilder::cuboid(half_extents.x, half_extents.y)
-                .collision_groups(InteractionGroups::new(0b0100, 0b1101))
+            SelectPart::Body => ColliderBuilder::new(collider_shape)
+                .collision_groups(groups(&[CollisionLayer::CarBody, team]))
+                .active_events(ActiveEvents::CONTACT_EVENTS)
                 .build(),
-            SelectPart::Wheel => ColliderBuilder::ball(half_extents.x)
-                .friction(8.0)
+            SelectPart::Wheel => ColliderBuilder::new(collider_shape)
+                .friction(WHEEL_FRICTION)
                 // .density(1.5)
-                .collision_groups(InteractionGroups::new(0b0010, 0b0011))
+                .collision_groups(groups(&[CollisionLayer::Wheel, team]))
+                .active_events(ActiveEvents::CONTACT_EVENTS)
                 .build(),
         };
         let coll_handle = coll_set.insert_with_parent(collider, body_handle, body_set);
@@ -59,12 +60,10 @@ impl CarPart {
             SelectPart::Body => CarPart::Body {
                 body_handle,
                 coll_handle,
-                joint_handle: None,
             },
             SelectPart::Wheel => CarPart::Wheel {
                 body_handle,
                 coll_handle,
-                joint_handle: None,
             },
         }
     }
@@ -82,63 +81,124 @@ impl CarPart {
         }
     }
 }
-#[derive(PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 enum CarComponents {
     WheelFront,
     WheelBack,
     CarBody,
 }
+
+impl CarComponents {
+    // position relative to the car body, when upright
+    fn offset(&self) -> Vector2<f32> {
+        match self {
+            CarComponents::CarBody => vector![0.0, 0.0],
+            CarComponents::WheelFront => vector![WHEEL_FRONT_X_OFFSET, WHEEL_FRONT_Y_OFFSET],
+            CarComponents::WheelBack => vector![WHEEL_BACK_X_OFFSET, WHEEL_BACK_Y_OFFSET],
+        }
+    }
+
+    fn layer(&self) -> CollisionLayer {
+        match self {
+            CarComponents::CarBody => CollisionLayer::CarBody,
+            _ => CollisionLayer::Wheel,
+        }
+    }
+}
+// what makes one car different from another
+#[derive(Clone, Debug)]
+pub struct CarSpec {
+    pub drivetrain: Drivetrain,
+    pub hitbox: HitboxPreset,
+    pub scale: f32, // of the whole car, wheels and hitbox alike
+}
+
+impl Default for CarSpec {
+    fn default() -> Self {
+        CarSpec {
+            drivetrain: Drivetrain::default(),
+            hitbox: HitboxPreset::default(),
+            scale: 1.0,
+        }
+    }
+}
+
 #[derive(PartialEq)]
 pub enum CarStates {
     Air,
     Ground,
+    Wall,
 }
 pub struct Car {
-    _velocity: Vector2<f32>,
-    position: Vector2<f32>,
+    pub id: CarId,
+    pub side: Side,
     parts: HashMap<CarComponents, CarPart>,
     _joint_handles: Vec<JointHandle>,
     state: CarStates,
+    color: Color,
+    skin: usize, // row in the car body atlas
+    boost: f32,
+    respawn_timer: Option<f32>, // in s, set while demolished
+    facing: f32, // 1.0 when the front is on the body's local +x side, air roll flips it
+    air_error: f32, // angular velocity error of the last air control step
+    spec: CarSpec,
+    throttle: f32, // analog, -1..1 along the car's forward vector
+    handbrake: bool,
+    tyres: HashMap<CarComponents, TyreState>, // wheels touching something after the last step
+    wheel_torques: HashMap<CarComponents, f32>, // drive or brake torque of the last drive call
+}
+
+// the side's cars start facing the opponent goal
+fn kickoff_facing(side: Side) -> f32 {
+    match side {
+        Side::Left => 1.0,
+        Side::Right => -1.0,
+    }
 }
 
 impl Car {
     pub fn new(
+        id: CarId,
+        side: Side,
+        spec: CarSpec,
         position: Vector2<f32>,
         body_set: &mut RigidBodySet,
         coll_set: &mut ColliderSet,
         joint_set: &mut JointSet,
     ) -> Car {
+        let facing = kickoff_facing(side);
+        let scale = spec.scale;
         let car_body = CarPart::new(
             SelectPart::Body,
             position,
-            vector![LENGTH / 2.0, HEIGHT / 2.0],
+            spec.hitbox.shape(facing, scale),
+            team_layer(side),
             body_set,
             coll_set,
         );
 
-        let wheel_front_position = position + vector![WHEEL_FRONT_X_OFFSET, WHEEL_FRONT_Y_OFFSET];
+        let wheel_front_offset = CarComponents::WheelFront.offset() * scale;
         let wheel_front = CarPart::new(
             SelectPart::Wheel,
-            wheel_front_position,
-            vector![WHEEL_RADIUS, 0.],
+            position + wheel_front_offset,
+            SharedShape::ball(WHEEL_RADIUS * scale),
+            team_layer(side),
             body_set,
             coll_set,
         );
 
-        let wheel_back_position = position + vector![WHEEL_BACK_X_OFFSET, WHEEL_BACK_Y_OFFSET];
+        let wheel_back_offset = CarComponents::WheelBack.offset() * scale;
         let wheel_back = CarPart::new(
             SelectPart::Wheel,
-            wheel_back_position,
-            vector![WHEEL_RADIUS, 0.],
+            position + wheel_back_offset,
+            SharedShape::ball(WHEEL_RADIUS * scale),
+            team_layer(side),
             body_set,
             coll_set,
         );
 
         // ASSEMBLE CAR:
-        let wheel_front_joint = BallJoint::new(
-            point![0.0, 0.0],
-            point![WHEEL_FRONT_X_OFFSET, WHEEL_FRONT_Y_OFFSET],
-        );
+        let wheel_front_joint = BallJoint::new(point![0.0, 0.0], Point::from(wheel_front_offset));
 
         let wheel_front_joint_handle = joint_set.insert(
             wheel_front.get_body_handle(),
@@ -146,10 +206,7 @@ impl Car {
             wheel_front_joint,
         );
 
-        let wheel_back_joint = BallJoint::new(
-            point![0.0, 0.0],
-            point![WHEEL_BACK_X_OFFSET, WHEEL_BACK_Y_OFFSET],
-        );
+        let wheel_back_joint = BallJoint::new(point![0.0, 0.0], Point::from(wheel_back_offset));
 
         let wheel_back_joint_handle = joint_set.insert(
             wheel_back.get_body_handle(),
@@ -164,21 +221,199 @@ impl Car {
         component_map.insert(CarComponents::WheelBack, wheel_back);
 
         Car {
-            position,
-            _velocity: vector![0.0, 0.0],
+            id,
+            side,
             parts: component_map,
             _joint_handles: vec![wheel_front_joint_handle, wheel_back_joint_handle],
             state: CarStates::Ground,
+            color: team_color(side),
+            skin: side.index(),
+            boost: MAX_BOOST / 3.0,
+            respawn_timer: None,
+            facing,
+            air_error: 0.0,
+            spec,
+            throttle: 0.0,
+            handbrake: false,
+            tyres: HashMap::new(),
+            wheel_torques: HashMap::new(),
+        }
+    }
+
+    // moves the whole car upright to a new position and stops it, facing the opponent goal
+    pub fn place(
+        &mut self,
+        position: Vector2<f32>,
+        body_set: &mut RigidBodySet,
+        coll_set: &mut ColliderSet,
+    ) {
+        self.set_facing(kickoff_facing(self.side), coll_set);
+        self.air_error = 0.0;
+        self.throttle = 0.0;
+        self.handbrake = false;
+        self.tyres.clear();
+        self.wheel_torques.clear();
+        for (component, part) in self.parts.iter() {
+            let body = body_set.get_mut(part.get_body_handle()).unwrap();
+            body.set_translation(position + component.offset() * self.spec.scale, true);
+            body.set_rotation(0.0, true);
+            body.set_linvel(vector![0.0, 0.0], true);
+            body.set_angvel(0.0, true);
         }
     }
 
+    fn wheel_radius(&self) -> f32 {
+        WHEEL_RADIUS * self.spec.scale
+    }
+
+    pub fn is_demolished(&self) -> bool {
+        self.respawn_timer.is_some()
+    }
+
+    // demolished for good, until the next round
+    pub fn is_knocked_out(&self) -> bool {
+        self.respawn_timer == Some(f32::INFINITY)
+    }
+
+    // takes the car out of play: its bodies freeze outside the arena and stop colliding
+    pub fn demolish(
+        &mut self,
+        respawn_time: f32,
+        body_set: &mut RigidBodySet,
+        coll_set: &mut ColliderSet,
+    ) {
+        self.place(
+            vector![ARENA_WIDTH / 2.0, -ARENA_HEIGHT],
+            body_set,
+            coll_set,
+        );
+        for part in self.parts.values() {
+            body_set[part.get_body_handle()].set_body_type(RigidBodyType::Static);
+            coll_set[part.get_coll_handle()].set_collision_groups(InteractionGroups::none());
+        }
+        self.respawn_timer = Some(respawn_time);
+    }
+
+    // counts down while demolished, true on the frame the car is due back
+    pub fn tick_respawn(&mut self, dt: f32) -> bool {
+        match self.respawn_timer.as_mut() {
+            Some(timer) => {
+                *timer -= dt;
+                *timer <= 0.0
+            }
+            None => false,
+        }
+    }
+
+    pub fn respawn(
+        &mut self,
+        position: Vector2<f32>,
+        body_set: &mut RigidBodySet,
+        coll_set: &mut ColliderSet,
+    ) {
+        for (component, part) in self.parts.iter() {
+            body_set[part.get_body_handle()].set_body_type(RigidBodyType::Dynamic);
+            coll_set[part.get_coll_handle()]
+                .set_collision_groups(groups(&[component.layer(), team_layer(self.side)]));
+        }
+        self.place(position, body_set, coll_set);
+        self.boost = MAX_BOOST / 3.0;
+        self.state = CarStates::Air;
+        self.respawn_timer = None;
+    }
+
+    pub fn get_velocity(&self, body_set: &RigidBodySet) -> Vector2<f32> {
+        *body_set[self.body_handle()].linvel()
+    }
+
     pub fn get_car_state(&self) -> CarStates {
         match self.state {
             CarStates::Air => CarStates::Air,
             CarStates::Ground => CarStates::Ground,
+            CarStates::Wall => CarStates::Wall,
         }
     }
 
+    pub fn register_colliders(&self, owners: &mut ColliderOwners) {
+        for (component, part) in self.parts.iter() {
+            let select = match component {
+                CarComponents::CarBody => SelectPart::Body,
+                _ => SelectPart::Wheel,
+            };
+            owners.insert(part.get_coll_handle(), ColliderOwner::Car(self.id, select));
+        }
+    }
+
+    pub fn body_handle(&self) -> RigidBodyHandle {
+        self.parts
+            .get(&CarComponents::CarBody)
+            .unwrap()
+            .get_body_handle()
+    }
+
+    // body and wheels, to move the whole car at once
+    pub fn body_handles(&self) -> Vec<RigidBodyHandle> {
+        self.parts
+            .values()
+            .map(|part| part.get_body_handle())
+            .collect()
+    }
+
+    pub fn collider_handles(&self) -> Vec<ColliderHandle> {
+        self.parts
+            .values()
+            .map(|part| part.get_coll_handle())
+            .collect()
+    }
+
+    pub fn get_speed(&self, body_set: &RigidBodySet) -> f32 {
+        self.get_velocity(body_set).norm()
+    }
+
+    // mean spin of both wheels, in rad/s
+    pub fn wheel_angvel(&self, body_set: &RigidBodySet) -> f32 {
+        let wheels = [CarComponents::WheelFront, CarComponents::WheelBack];
+        wheels
+            .iter()
+            .map(|wheel| body_set[self.parts.get(wheel).unwrap().get_body_handle()].angvel())
+            .sum::<f32>()
+            / wheels.len() as f32
+    }
+
+    pub fn is_supersonic(&self, body_set: &RigidBodySet) -> bool {
+        self.get_speed(body_set) >= SUPERSONIC_SPEED
+    }
+
+    pub fn get_boost(&self) -> f32 {
+        self.boost
+    }
+
+    pub fn add_boost(&mut self, amount: f32) {
+        self.boost = (self.boost + amount).min(MAX_BOOST);
+    }
+
+    pub fn set_boost(&mut self, amount: f32) {
+        self.boost = amount.clamp(0.0, MAX_BOOST);
+    }
+
+    // drains the tank for one frame of boosting, false when empty
+    pub fn use_boost(&mut self, dt: f32) -> bool {
+        if self.boost <= 0.0 {
+            return false;
+        }
+        self.boost = (self.boost - BOOST_CONSUMPTION * dt).max(0.0);
+        true
+    }
+
+    pub fn get_position(&self, body_set: &RigidBodySet) -> Vector2<f32> {
+        *body_set[self
+            .parts
+            .get(&CarComponents::CarBody)
+            .unwrap()
+            .get_body_handle()]
+        .translation()
+    }
+
     pub fn query_wheels_collision(
         &self,
         other_coll_handle: ColliderHandle,
@@ -216,32 +451,106 @@ impl Car {
     }
 
     pub fn set_car_state(&mut self, new_state: CarStates) {
+        if new_state != CarStates::Air {
+            self.air_error = 0.0;
+        }
         self.state = new_state;
     }
 
-    pub fn drive(&self, torque: f32, body_set: &mut RigidBodySet) {
-        let wheel_front_body = body_set
-            .get_mut(
-                self.parts
-                    .get(&CarComponents::WheelFront)
-                    .unwrap()
-                    .get_body_handle(),
-            )
-            .unwrap();
-        wheel_front_body.apply_torque(torque, true);
-
-        let wheel_back_body = body_set
-            .get_mut(
-                self.parts
-                    .get(&CarComponents::WheelBack)
-                    .unwrap()
-                    .get_body_handle(),
-            )
-            .unwrap();
-        wheel_back_body.apply_torque(torque, true);
+    // input -1..1 along the body's local x axis (right key = +1 for an upright car).
+    // call every frame, wheels only get torque with the car on the ground or a wall.
+    pub fn drive(
+        &mut self,
+        input: f32,
+        handbrake: bool,
+        tuning: &Tuning,
+        dt: f32,
+        body_set: &mut RigidBodySet,
+    ) {
+        self.handbrake = handbrake;
+        self.throttle = ramp_throttle(self.throttle, input * self.facing, dt);
+        let forward_speed = self.get_velocity(body_set).dot(&self.forward(body_set));
+        let drivetrain = &self.spec.drivetrain;
+        let command = drivetrain.command(self.throttle, forward_speed);
+
+        // the wheel at the car's front depends on which way it faces:
+        let (front, rear) = if self.is_facing_right() {
+            (CarComponents::WheelFront, CarComponents::WheelBack)
+        } else {
+            (CarComponents::WheelBack, CarComponents::WheelFront)
+        };
+        let (front_split, rear_split) = drivetrain.layout.split();
+
+        self.wheel_torques.clear();
+        for (wheel, split) in [(front, front_split), (rear, rear_split)] {
+            let part = self.parts.get(&wheel).unwrap();
+            if self.state == CarStates::Air {
+                continue;
+            }
+
+            let body = body_set.get_mut(part.get_body_handle()).unwrap();
+            let forward_angvel = body.angvel() * self.facing;
+            let torque = match command {
+                _ if handbrake && wheel == rear => drivetrain.brake(forward_angvel),
+                DriveCommand::Throttle(throttle) => {
+                    throttle * tuning.drive_torque * split * drivetrain.torque_share(forward_angvel)
+                }
+                DriveCommand::Brake => drivetrain.brake(forward_angvel),
+                DriveCommand::Coast => 0.0,
+            };
+            body.apply_torque(torque * self.facing, true);
+            self.wheel_torques.insert(wheel, torque);
+        }
+    }
+
+    // reads the wheel contacts of the last step and sets the wheel friction for the next one.
+    // call after the physics step.
+    pub fn update_tyres(
+        &mut self,
+        tuning: &Tuning,
+        dt: f32,
+        body_set: &RigidBodySet,
+        coll_set: &mut ColliderSet,
+        narrow_phase: &NarrowPhase,
+    ) {
+        let friction = if self.handbrake {
+            tuning.wheel_friction * self.spec.drivetrain.handbrake_friction
+        } else {
+            tuning.wheel_friction
+        };
+
+        for wheel in [CarComponents::WheelFront, CarComponents::WheelBack] {
+            let handle = self.parts.get(&wheel).unwrap().get_coll_handle();
+            match tyre_state(
+                handle,
+                self.wheel_radius(),
+                dt,
+                body_set,
+                coll_set,
+                narrow_phase,
+            ) {
+                Some(mut tyre) => {
+                    let torque = self.wheel_torques.get(&wheel).copied().unwrap_or(0.0);
+                    tyre.overpowered =
+                        torque.abs() > tyre.traction_limit(friction, self.wheel_radius());
+                    coll_set[handle].set_friction(friction * tyre.grip());
+                    self.tyres.insert(wheel, tyre);
+                }
+                None => {
+                    // full grip again for the next touchdown:
+                    coll_set[handle].set_friction(friction);
+                    self.tyres.remove(&wheel);
+                }
+            }
+        }
+    }
+
+    pub fn is_wheelspinning(&self) -> bool {
+        self.tyres.values().any(|tyre| tyre.is_spinning())
     }
 
-    pub fn jump(&self, body_set: &mut RigidBodySet) {
+    // true when the car actually jumped
+    pub fn jump(&self, impulse: f32, body_set: &mut RigidBodySet) -> bool {
         match self.state {
             CarStates::Ground => {
                 let rigid_body = body_set
@@ -252,54 +561,127 @@ impl Car {
                             .get_body_handle(),
                     )
                     .unwrap();
-                rigid_body.apply_impulse(vector![0.0, -6000.0], true);
+                rigid_body.apply_impulse(vector![0.0, -impulse], true);
+                true
             }
-            CarStates::Air => {}
+            CarStates::Air | CarStates::Wall => false,
         }
     }
 
+    // unit vector from the back to the front of the car, in world space
+    pub fn forward(&self, body_set: &RigidBodySet) -> Vector2<f32> {
+        body_set[self.body_handle()]
+            .rotation()
+            .transform_vector(&vector![self.facing, 0.0])
+    }
+
+    pub fn is_facing_right(&self) -> bool {
+        self.facing > 0.0
+    }
+
     pub fn boost(&self, force: f32, body_set: &mut RigidBodySet) {
-        let rigid_body = body_set
-            .get_mut(
-                self.parts
-                    .get(&CarComponents::CarBody)
-                    .unwrap()
-                    .get_body_handle(),
-            )
-            .unwrap();
+        let forward = self.forward(body_set);
+        let rigid_body = body_set.get_mut(self.body_handle()).unwrap();
+        rigid_body.apply_impulse(forward * force, true);
+    }
 
-        let rotation = rigid_body.rotation();
+    // nozzle position at the rear of the body, and the exhaust direction
+    pub fn boost_exhaust(&self, body_set: &RigidBodySet) -> (Vector2<f32>, Vector2<f32>) {
+        let exhaust = -self.forward(body_set);
+        let position = body_set[self.body_handle()].translation();
+        let rear = self.spec.hitbox.bounds(1.0, self.spec.scale).0.x;
+        (position + exhaust * -rear, exhaust)
+    }
+
+    // lowest points of the wheels, where they touch a flat floor
+    pub fn wheel_ground_points(&self, body_set: &RigidBodySet) -> Vec<Vector2<f32>> {
+        [CarComponents::WheelFront, CarComponents::WheelBack]
+            .iter()
+            .map(|wheel| {
+                body_set[self.parts.get(wheel).unwrap().get_body_handle()].translation()
+                    + vector![0.0, self.wheel_radius()]
+            })
+            .collect()
+    }
 
-        let final_rot = if rotation.angle() < -0.5 * PI || rotation.angle() > 0.5 * PI {
-            utils::turn_around(rotation)
+    // PD control of the car's spin towards input (-1..1) times the max air angular velocity.
+    // scaled by the inertia of the whole car, so responsiveness is the same for any car mass.
+    pub fn air_control(
+        &mut self,
+        input: f32,
+        tuning: &Tuning,
+        dt: f32,
+        body_set: &mut RigidBodySet,
+    ) {
+        let body = &body_set[self.body_handle()];
+        let inv_inertia_sqrt = body.mass_properties().inv_principal_inertia_sqrt;
+        let mut inertia = if inv_inertia_sqrt > 0.0 {
+            1.0 / (inv_inertia_sqrt * inv_inertia_sqrt)
         } else {
-            *rotation
+            0.0
         };
+        for (component, part) in self.parts.iter() {
+            if let CarComponents::WheelFront | CarComponents::WheelBack = component {
+                let offset = component.offset() * self.spec.scale;
+                inertia += body_set[part.get_body_handle()].mass() * offset.norm_squared();
+            }
+        }
 
-        let boost_vector = final_rot.transform_vector(&vector!(0.0, 1.0));
+        let target = input.clamp(-1.0, 1.0) * tuning.air_max_angvel;
+        let error = target - body.angvel();
+        let error_rate = (error - self.air_error) / dt.max(f32::EPSILON);
+        self.air_error = error;
 
-        rigid_body.apply_impulse(boost_vector * force, true);
+        let torque =
+            inertia * (tuning.air_responsiveness * error + AIR_DERIVATIVE_GAIN * error_rate);
+        body_set
+            .get_mut(self.body_handle())
+            .unwrap()
+            .apply_torque(torque, true);
     }
 
-    pub fn spin(&self, torque: f32, body_set: &mut RigidBodySet) {
-        let rigid_body = body_set
-            .get_mut(
-                self.parts
-                    .get(&CarComponents::CarBody)
+    // mirrors the car mid air to face the other way. the wheels are symmetric, so only the
+    // body hitbox is swapped for its mirror image.
+    pub fn air_roll(&mut self, coll_set: &mut ColliderSet) {
+        if self.state == CarStates::Air {
+            self.set_facing(-self.facing, coll_set);
+        }
+    }
+
+    fn set_facing(&mut self, facing: f32, coll_set: &mut ColliderSet) {
+        if facing != self.facing {
+            let handle = self
+                .parts
+                .get(&CarComponents::CarBody)
+                .unwrap()
+                .get_coll_handle();
+            coll_set[handle].set_shape(self.spec.hitbox.shape(facing, self.spec.scale));
+        }
+        self.facing = facing;
+    }
+
+    pub fn apply_tuning(
+        &self,
+        tuning: &Tuning,
+        body_set: &mut RigidBodySet,
+        coll_set: &mut ColliderSet,
+    ) {
+        for (component, part) in self.parts.iter() {
+            let body = body_set.get_mut(part.get_body_handle()).unwrap();
+            body.set_linear_damping(tuning.car_linear_damping);
+            body.set_angular_damping(tuning.car_angular_damping);
+            body.wake_up(true);
+
+            if let CarComponents::WheelFront | CarComponents::WheelBack = component {
+                coll_set
+                    .get_mut(part.get_coll_handle())
                     .unwrap()
-                    .get_body_handle(),
-            )
-            .unwrap();
-        rigid_body.apply_torque_impulse(torque, true);
+                    .set_friction(tuning.wheel_friction);
+            }
+        }
     }
 
-    pub fn draw(&self, body_set: &RigidBodySet) {
-        let translation = body_set[self
-            .parts
-            .get(&CarComponents::CarBody)
-            .unwrap()
-            .get_body_handle()]
-        .translation();
+    pub fn draw(&self, body_set: &RigidBodySet, assets: &Assets) {
         let rotation = body_set[self
             .parts
             .get(&CarComponents::CarBody)
@@ -307,13 +689,36 @@ impl Car {
             .get_body_handle()]
         .rotation()
         .angle();
-        utils::draw_line_center(
-            pos_vec_mtr_to_pxl(vector![translation.x, translation.y]),
-            rotation,
-            size_mtr_to_pxl(HEIGHT),
-            size_mtr_to_pxl(LENGTH),
-            RED,
-        );
+        // the sprite covers the hitbox bounds, without a sprite the hitbox itself is drawn:
+        let body_position = body_set[self.body_handle()].position();
+        match assets.car_body_frame(self.skin) {
+            Some((atlas, frame)) => {
+                let (mins, maxs) = self.spec.hitbox.bounds(self.facing, self.spec.scale);
+                let center = body_position * Point::from((mins + maxs) / 2.0);
+                draw_sprite_center(
+                    atlas,
+                    Some(frame),
+                    center.coords,
+                    maxs - mins,
+                    rotation,
+                    !self.is_facing_right(),
+                );
+            }
+            None => {
+                for outline in self.spec.hitbox.outlines(self.facing, self.spec.scale) {
+                    let pixels: Vec<Vec2> = outline
+                        .iter()
+                        .map(|point| {
+                            let pixel = pos_vec_mtr_to_pxl((body_position * point).coords);
+                            vec2(pixel.x, pixel.y)
+                        })
+                        .collect();
+                    for i in 1..pixels.len() - 1 {
+                        draw_triangle(pixels[0], pixels[i], pixels[i + 1], self.color);
+                    }
+                }
+            }
+        }
 
         for (component, part) in self.parts.iter() {
             match component {
@@ -321,11 +726,24 @@ impl Car {
                     let translation = body_set[part.get_body_handle()].translation();
                     let rotation = body_set[part.get_body_handle()].rotation().angle();
 
+                    if let Some(texture) = assets.wheel {
+                        let diameter = self.wheel_radius() * 2.0;
+                        draw_sprite_center(
+                            texture,
+                            None,
+                            *translation,
+                            vector![diameter, diameter],
+                            rotation,
+                            false,
+                        );
+                        continue;
+                    }
+
                     draw_poly(
                         pos_x_mtr_to_pxl(translation.x),
                         pos_y_mtr_to_pxl(translation.y),
                         8,
-                        size_mtr_to_pxl(WHEEL_RADIUS),
+                        size_mtr_to_pxl(self.wheel_radius()),
                         rotation.to_degrees(),
                         ORANGE,
                     );
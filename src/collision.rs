@@ -0,0 +1,117 @@
+use crate::*;
+
+// COLLISION LAYERS:
+// every collider is a member of one or more layers. which layers touch each other is declared
+// once in INTERACTIONS, the rapier filter bits are derived from it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CollisionLayer {
+    Arena,
+    Wheel,
+    CarBody,
+    Ball,
+    GoalSensor,
+    BoostPad,
+    Trigger,
+    TeamA, // team tags, only matter for layers that list them below
+    TeamB,
+}
+
+impl CollisionLayer {
+    pub fn bit(self) -> u32 {
+        1 << self as u32
+    }
+
+    // all layers this one interacts with, as a rapier filter
+    fn filter(self) -> u32 {
+        INTERACTIONS
+            .iter()
+            .filter_map(|&(a, b)| match (a == self, b == self) {
+                (true, _) => Some(b.bit()),
+                (_, true) => Some(a.bit()),
+                _ => None,
+            })
+            .fold(0, |filter, bit| filter | bit)
+    }
+}
+
+// symmetric: (a, b) lets a touch b and b touch a
+const INTERACTIONS: &[(CollisionLayer, CollisionLayer)] = &[
+    (CollisionLayer::Ball, CollisionLayer::Arena),
+    (CollisionLayer::Ball, CollisionLayer::Ball),
+    (CollisionLayer::Ball, CollisionLayer::CarBody),
+    (CollisionLayer::Ball, CollisionLayer::GoalSensor),
+    (CollisionLayer::Ball, CollisionLayer::Trigger),
+    (CollisionLayer::CarBody, CollisionLayer::Arena),
+    (CollisionLayer::CarBody, CollisionLayer::CarBody),
+    (CollisionLayer::CarBody, CollisionLayer::BoostPad),
+    (CollisionLayer::CarBody, CollisionLayer::Trigger),
+    (CollisionLayer::Wheel, CollisionLayer::Arena),
+    (CollisionLayer::Wheel, CollisionLayer::Wheel),
+];
+
+pub fn groups(layers: &[CollisionLayer]) -> InteractionGroups {
+    let memberships = layers.iter().fold(0, |bits, layer| bits | layer.bit());
+    let filter = layers.iter().fold(0, |bits, layer| bits | layer.filter());
+    InteractionGroups::new(memberships, filter)
+}
+
+pub fn team_layer(side: Side) -> CollisionLayer {
+    match side {
+        Side::Left => CollisionLayer::TeamA,
+        Side::Right => CollisionLayer::TeamB,
+    }
+}
+
+// gameplay rules the matrix must keep:
+#[cfg(test)]
+mod tests {
+    use super::CollisionLayer::*;
+    use super::*;
+
+    fn interacts(a: &[CollisionLayer], b: &[CollisionLayer]) -> bool {
+        groups(a).test(groups(b))
+    }
+
+    #[test]
+    fn ball_hits_arena_cars_and_balls() {
+        assert!(interacts(&[Ball], &[Arena]));
+        assert!(interacts(&[Ball], &[CarBody]));
+        assert!(interacts(&[Ball], &[Ball]));
+        assert!(interacts(&[Ball], &[GoalSensor]));
+    }
+
+    #[test]
+    fn cars_hit_arena() {
+        assert!(interacts(&[Wheel], &[Arena]));
+        assert!(interacts(&[CarBody], &[Arena]));
+    }
+
+    #[test]
+    fn wheels_only_hit_arena_and_wheels() {
+        assert!(!interacts(&[Wheel], &[Ball]));
+        assert!(!interacts(&[Wheel], &[CarBody]));
+        assert!(!interacts(&[Wheel], &[GoalSensor]));
+        assert!(!interacts(&[Wheel], &[BoostPad]));
+    }
+
+    #[test]
+    fn sensors_only_see_their_owners() {
+        assert!(!interacts(&[CarBody], &[GoalSensor]));
+        assert!(!interacts(&[Ball], &[BoostPad]));
+        assert!(interacts(&[CarBody], &[BoostPad]));
+    }
+
+    #[test]
+    fn balls_and_car_bodies_enter_triggers() {
+        assert!(interacts(&[Ball], &[Trigger]));
+        assert!(interacts(&[CarBody], &[Trigger]));
+        assert!(!interacts(&[Wheel], &[Trigger]));
+        assert!(!interacts(&[Arena], &[Trigger]));
+    }
+
+    #[test]
+    fn team_tags_change_nothing() {
+        assert!(interacts(&[CarBody, TeamA], &[CarBody, TeamB]));
+        assert!(interacts(&[CarBody, TeamA], &[Ball]));
+    }
+}
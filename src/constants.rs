@@ -1,29 +1,82 @@
-// COLLISION GROUPS
-// 1 ball
-// 2 body
-// 3 wheels
+use crate::*;
 
+// COLLISION GROUPS: see collision.rs
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum Side {
     Left,
     Right,
 }
 
+impl Side {
+    pub fn opponent(self) -> Side {
+        match self {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+
+    pub fn index(self) -> usize {
+        match self {
+            Side::Left => 0,
+            Side::Right => 1,
+        }
+    }
+}
+
 // WINDOW:
 pub const ASPECT_RATIO: f32 = 16.0 / 9.0;
 pub const PIXEL_W: i32 = 1280;
 pub const PIXEL_H: i32 = (PIXEL_W as f32 / ASPECT_RATIO) as i32;
 
+pub const LETTERBOX_COLOR: Color = BLACK;
+
 pub const ARENA_WIDTH: f32 = 105.0; // in m
 pub const ARENA_HEIGHT: f32 = 40.0; // in m
 
-pub const PIXELS_PER_METRE: f32 = PIXEL_W as f32 / ARENA_WIDTH;
-
 pub const BALL_RADIUS: f32 = 2.5; // in m
 
+// WORLD:
+pub const GRAVITY: f32 = 29.81;
+
+// MATCH:
+pub const MATCH_DURATION: f32 = 300.0; // in s
+pub const TRAJECTORY_HORIZON: f32 = 3.0; // in s of predicted ball path
+pub const MAX_BALLS: usize = 3; // in play at once
+
+// GOALS:
+pub const GOAL_HEIGHT: f32 = 12.0; // in m
+pub const GOAL_DEPTH: f32 = 8.0; // in m, from the wall to the goal line
+pub const GOAL_SENSOR_DEPTH: f32 = 2.0; // in m, at the back of the goal
+pub const CROSSBAR_THICKNESS: f32 = 1.0; // in m
+pub const HOOP_WIDTH: f32 = 9.0; // in m, inside the rim
+pub const HOOP_DEPTH: f32 = 4.0; // in m, from the rim down to the bottom of the basket
+pub const HOOP_HEIGHT: f32 = 14.0; // in m, from the floor to the bottom of the basket
+
+// BOOST:
+pub const MAX_BOOST: f32 = 100.0;
+pub const BOOST_CONSUMPTION: f32 = 33.0; // per second
+pub const BOOST_PAD_AMOUNT: f32 = 50.0;
+pub const BOOST_PAD_COOLDOWN: f32 = 6.0; // in s
+pub const BOOST_PAD_SIZE: f32 = 2.0; // in m
+
 // BALL:
 pub const BALL_RESTITUTION: f32 = 0.7;
 pub const BALL_GRAV_SCALE: f32 = 0.2;
 pub const BALL_DENSITY: f32 = 0.5;
 
 // PLAYER:
+#[allow(dead_code)] // cars bounce with the default restitution for now
 pub const PLAYER_RESTITUTION: f32 = 0.7;
+pub const CAR_ADDITIONAL_MASS: f32 = 50.0;
+pub const CAR_LINEAR_DAMPING: f32 = 0.5;
+pub const CAR_ANGULAR_DAMPING: f32 = 5.0;
+pub const WHEEL_FRICTION: f32 = 8.0;
+pub const DRIVE_TORQUE: f32 = 5000.0;
+pub const JUMP_IMPULSE: f32 = 6000.0;
+pub const BOOST_FORCE: f32 = 300.0;
+pub const AIR_MAX_ANGVEL: f32 = 5.5; // in rad/s
+pub const AIR_RESPONSIVENESS: f32 = 8.0; // in 1/s
+pub const SUPERSONIC_SPEED: f32 = 30.0; // in m/s
+pub const BUMP_STRENGTH: f32 = 120.0; // impulse per m/s of closing speed
+pub const RESPAWN_TIME: f32 = 3.0; // in s, after a demolition
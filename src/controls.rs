@@ -0,0 +1,112 @@
+use crate::*;
+
+// CONTROLS:
+// keyboard layout per local player
+#[derive(Clone, PartialEq, Debug)]
+pub struct Controls {
+    pub left: KeyCode,
+    pub right: KeyCode,
+    pub jump: KeyCode,
+    pub boost: KeyCode,
+    pub air_roll: KeyCode,
+    pub handbrake: KeyCode,
+}
+
+// keys that can be bound, stored by their name in the settings file
+const BINDABLE_KEYS: [KeyCode; 46] = [
+    KeyCode::A,
+    KeyCode::B,
+    KeyCode::C,
+    KeyCode::D,
+    KeyCode::E,
+    KeyCode::F,
+    KeyCode::G,
+    KeyCode::H,
+    KeyCode::I,
+    KeyCode::J,
+    KeyCode::K,
+    KeyCode::L,
+    KeyCode::M,
+    KeyCode::N,
+    KeyCode::O,
+    KeyCode::P,
+    KeyCode::Q,
+    KeyCode::R,
+    KeyCode::S,
+    KeyCode::T,
+    KeyCode::U,
+    KeyCode::V,
+    KeyCode::W,
+    KeyCode::X,
+    KeyCode::Y,
+    KeyCode::Z,
+    KeyCode::Key0,
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+    KeyCode::Left,
+    KeyCode::Right,
+    KeyCode::Up,
+    KeyCode::Down,
+    KeyCode::Space,
+    KeyCode::Enter,
+    KeyCode::LeftShift,
+    KeyCode::RightShift,
+    KeyCode::LeftControl,
+    KeyCode::RightControl,
+];
+
+impl Controls {
+    pub fn for_player(index: usize) -> Controls {
+        match index {
+            0 => Controls {
+                left: KeyCode::Left,
+                right: KeyCode::Right,
+                jump: KeyCode::Up,
+                boost: KeyCode::Space,
+                air_roll: KeyCode::Down,
+                handbrake: KeyCode::RightShift,
+            },
+            _ => Controls {
+                left: KeyCode::A,
+                right: KeyCode::D,
+                jump: KeyCode::W,
+                boost: KeyCode::LeftShift,
+                air_roll: KeyCode::S,
+                handbrake: KeyCode::LeftControl,
+            },
+        }
+    }
+
+    pub fn fields_mut(&mut self) -> [(&'static str, &mut KeyCode); 6] {
+        [
+            ("left", &mut self.left),
+            ("right", &mut self.right),
+            ("jump", &mut self.jump),
+            ("boost", &mut self.boost),
+            ("air_roll", &mut self.air_roll),
+            ("handbrake", &mut self.handbrake),
+        ]
+    }
+}
+
+pub fn key_name(key: KeyCode) -> String {
+    format!("{:?}", key)
+}
+
+pub fn parse_key(name: &str) -> Option<KeyCode> {
+    BINDABLE_KEYS
+        .iter()
+        .copied()
+        .find(|key| key_name(*key) == name)
+}
+
+pub fn is_bindable(key: KeyCode) -> bool {
+    BINDABLE_KEYS.contains(&key)
+}
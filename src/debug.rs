@@ -0,0 +1,201 @@
+use crate::*;
+
+// PHYSICS DEBUG OVERLAY:
+// draws what rapier sees rather than what the sprites show. toggled with F1.
+const LINE_THICKNESS: f32 = 1.5; // in pixels
+const CONTACT_RADIUS: f32 = 0.25; // in m
+const NORMAL_LENGTH: f32 = 1.5; // in m
+const VELOCITY_SCALE: f32 = 0.2; // m drawn per m/s
+const SLEEPING_COLOR: Color = DARKGRAY;
+const GROUP_COLORS: [Color; 8] = [WHITE, ORANGE, RED, YELLOW, PINK, SKYBLUE, LIME, VIOLET];
+
+pub struct DebugRenderer {
+    pub enabled: bool,
+    pub show_shapes: bool,
+    pub show_aabbs: bool,
+    pub show_contacts: bool,
+    pub show_joints: bool,
+    pub show_velocities: bool,
+}
+
+impl DebugRenderer {
+    pub fn new() -> DebugRenderer {
+        DebugRenderer {
+            enabled: false,
+            show_shapes: true,
+            show_aabbs: true,
+            show_contacts: true,
+            show_joints: true,
+            show_velocities: true,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+    }
+
+    pub fn draw(
+        &self,
+        body_set: &RigidBodySet,
+        coll_set: &ColliderSet,
+        joint_set: &JointSet,
+        narrow_phase: &NarrowPhase,
+    ) {
+        if !self.enabled {
+            return;
+        }
+
+        for (_, collider) in coll_set.iter() {
+            let sleeping = collider
+                .parent()
+                .is_some_and(|handle| body_set[handle].is_sleeping());
+            let color = if sleeping {
+                SLEEPING_COLOR
+            } else {
+                group_color(collider.collision_groups())
+            };
+
+            if self.show_shapes {
+                draw_shape(collider.shape(), collider.position(), color);
+            }
+            if self.show_aabbs {
+                let aabb = collider.compute_aabb();
+                draw_rectangle_lines(
+                    pos_x_mtr_to_pxl(aabb.mins.x),
+                    pos_y_mtr_to_pxl(aabb.mins.y),
+                    size_mtr_to_pxl(aabb.maxs.x - aabb.mins.x),
+                    size_mtr_to_pxl(aabb.maxs.y - aabb.mins.y),
+                    1.0,
+                    Color::new(color.r, color.g, color.b, 0.3),
+                );
+            }
+        }
+
+        if self.show_velocities {
+            for (_, body) in body_set.iter() {
+                if body.is_dynamic() && !body.is_sleeping() {
+                    let from = Point::from(*body.translation());
+                    draw_line_mtr(from, from + body.linvel() * VELOCITY_SCALE, GREEN);
+                }
+            }
+        }
+
+        if self.show_joints {
+            for (_, joint) in joint_set.iter() {
+                let (anchor1, anchor2) = joint_anchors(&joint.params);
+                let world1 = body_set[joint.body1].position() * anchor1;
+                let world2 = body_set[joint.body2].position() * anchor2;
+                draw_line_mtr(world1, world2, MAGENTA);
+                draw_circle_mtr(world1, CONTACT_RADIUS, MAGENTA);
+                draw_circle_mtr(world2, CONTACT_RADIUS * 0.6, WHITE);
+            }
+        }
+
+        if self.show_contacts {
+            for pair in narrow_phase.contact_pairs() {
+                if !pair.has_any_active_contact {
+                    continue;
+                }
+                for manifold in pair.manifolds.iter() {
+                    for contact in manifold.data.solver_contacts.iter() {
+                        draw_circle_mtr(contact.point, CONTACT_RADIUS, RED);
+                        draw_line_mtr(
+                            contact.point,
+                            contact.point + manifold.data.normal * NORMAL_LENGTH,
+                            RED,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+// colour of the lowest membership bit, so each collision layer gets its own colour
+fn group_color(groups: InteractionGroups) -> Color {
+    let index = groups.memberships.trailing_zeros() as usize;
+    GROUP_COLORS[index % GROUP_COLORS.len()]
+}
+
+fn joint_anchors(params: &JointParams) -> (Point<f32>, Point<f32>) {
+    match params {
+        JointParams::BallJoint(joint) => (joint.local_anchor1, joint.local_anchor2),
+        JointParams::PrismaticJoint(joint) => (joint.local_anchor1, joint.local_anchor2),
+        JointParams::FixedJoint(joint) => (
+            Point::from(joint.local_frame1.translation.vector),
+            Point::from(joint.local_frame2.translation.vector),
+        ),
+    }
+}
+
+fn draw_shape(shape: &dyn Shape, position: &Isometry<f32>, color: Color) {
+    if let Some(ball) = shape.as_ball() {
+        let center = position * Point::origin();
+        draw_circle_lines(
+            pos_x_mtr_to_pxl(center.x),
+            pos_y_mtr_to_pxl(center.y),
+            size_mtr_to_pxl(ball.radius),
+            LINE_THICKNESS,
+            color,
+        );
+        // radius line shows the rotation:
+        draw_line_mtr(center, position * point![ball.radius, 0.0], color);
+    } else if let Some(cuboid) = shape.as_cuboid() {
+        let half = cuboid.half_extents;
+        draw_polygon_mtr(
+            &[
+                point![-half.x, -half.y],
+                point![half.x, -half.y],
+                point![half.x, half.y],
+                point![-half.x, half.y],
+            ],
+            position,
+            color,
+        );
+    } else if let Some(polygon) = shape.as_convex_polygon() {
+        draw_polygon_mtr(polygon.points(), position, color);
+    } else if let Some(compound) = shape.as_compound() {
+        for (sub_position, sub_shape) in compound.shapes() {
+            draw_shape(&**sub_shape, &(position * sub_position), color);
+        }
+    } else {
+        let aabb = shape.compute_aabb(position);
+        draw_polygon_mtr(
+            &[
+                aabb.mins,
+                point![aabb.maxs.x, aabb.mins.y],
+                aabb.maxs,
+                point![aabb.mins.x, aabb.maxs.y],
+            ],
+            &Isometry::identity(),
+            color,
+        );
+    }
+}
+
+fn draw_polygon_mtr(points: &[Point<f32>], position: &Isometry<f32>, color: Color) {
+    for (i, point) in points.iter().enumerate() {
+        let next = points[(i + 1) % points.len()];
+        draw_line_mtr(position * point, position * next, color);
+    }
+}
+
+fn draw_line_mtr(from: Point<f32>, to: Point<f32>, color: Color) {
+    draw_line(
+        pos_x_mtr_to_pxl(from.x),
+        pos_y_mtr_to_pxl(from.y),
+        pos_x_mtr_to_pxl(to.x),
+        pos_y_mtr_to_pxl(to.y),
+        LINE_THICKNESS,
+        color,
+    );
+}
+
+fn draw_circle_mtr(center: Point<f32>, radius: f32, color: Color) {
+    draw_circle(
+        pos_x_mtr_to_pxl(center.x),
+        pos_y_mtr_to_pxl(center.y),
+        size_mtr_to_pxl(radius),
+        color,
+    );
+}
@@ -0,0 +1,111 @@
+use crate::*;
+
+// DRIVETRAIN:
+// turns the drive input into wheel torques. the input is relative to the car: towards its front
+// it throttles, against its motion it brakes and once (nearly) stopped it reverses.
+const THROTTLE_RATE: f32 = 4.0; // per s, how fast the analog throttle follows the input
+const BRAKE_SWITCH_SPEED: f32 = 1.0; // in m/s, below this an opposite input reverses instead of braking
+const BRAKE_FADE_ANGVEL: f32 = 2.0; // in rad/s, brake torque fades out below this to avoid jitter
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DriveLayout {
+    Front, // FWD
+    Rear,  // RWD
+    All,   // AWD
+}
+
+impl DriveLayout {
+    pub const ALL: [DriveLayout; 3] = [DriveLayout::All, DriveLayout::Front, DriveLayout::Rear];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DriveLayout::Front => "fwd",
+            DriveLayout::Rear => "rwd",
+            DriveLayout::All => "awd",
+        }
+    }
+
+    // drive torque multiples for the (front, rear) wheel, every layout gets the same total
+    pub fn split(self) -> (f32, f32) {
+        match self {
+            DriveLayout::Front => (2.0, 0.0),
+            DriveLayout::Rear => (0.0, 2.0),
+            DriveLayout::All => (1.0, 1.0),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Drivetrain {
+    pub layout: DriveLayout,
+    pub torque_curve: Vec<(f32, f32)>, // (wheel angular velocity in rad/s, share of the drive torque)
+    pub brake_torque: f32,             // per wheel
+    pub reverse_factor: f32,           // share of the drive torque in reverse
+    pub handbrake_friction: f32,       // share of the wheel friction left with the handbrake pulled
+}
+
+impl Default for Drivetrain {
+    fn default() -> Self {
+        Drivetrain {
+            layout: DriveLayout::All,
+            torque_curve: vec![(0.0, 1.0), (10.0, 0.9), (16.0, 0.5), (20.0, 0.0)],
+            brake_torque: 4000.0,
+            reverse_factor: 0.6,
+            handbrake_friction: 0.3,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DriveCommand {
+    Throttle(f32), // -1..1, negative in reverse
+    Brake,
+    Coast,
+}
+
+impl Drivetrain {
+    // linear in between the curve points, flat past the ends
+    pub fn torque_share(&self, wheel_angvel: f32) -> f32 {
+        let speed = wheel_angvel.abs();
+        let curve = &self.torque_curve;
+        match curve
+            .iter()
+            .position(|(point_speed, _)| *point_speed > speed)
+        {
+            Some(0) => curve[0].1,
+            Some(i) => {
+                let (speed_a, share_a) = curve[i - 1];
+                let (speed_b, share_b) = curve[i];
+                share_a + (share_b - share_a) * (speed - speed_a) / (speed_b - speed_a)
+            }
+            None => curve.last().map_or(0.0, |(_, share)| *share),
+        }
+    }
+
+    // input and speed both along the car's forward vector
+    pub fn command(&self, throttle: f32, forward_speed: f32) -> DriveCommand {
+        if throttle == 0.0 {
+            DriveCommand::Coast
+        } else if throttle.signum() != forward_speed.signum()
+            && forward_speed.abs() > BRAKE_SWITCH_SPEED
+        {
+            DriveCommand::Brake
+        } else if throttle < 0.0 {
+            DriveCommand::Throttle(throttle * self.reverse_factor)
+        } else {
+            DriveCommand::Throttle(throttle)
+        }
+    }
+
+    pub fn brake(&self, wheel_angvel: f32) -> f32 {
+        -wheel_angvel.signum()
+            * self.brake_torque
+            * (wheel_angvel.abs() / BRAKE_FADE_ANGVEL).min(1.0)
+    }
+}
+
+// moves the analog throttle towards the input
+pub fn ramp_throttle(throttle: f32, input: f32, dt: f32) -> f32 {
+    let step = THROTTLE_RATE * dt;
+    throttle + (input - throttle).clamp(-step, step)
+}
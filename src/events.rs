@@ -0,0 +1,332 @@
+use crate::car::SelectPart;
+use crate::nalgebra::Vector2;
+use crate::*;
+use rapier2d::crossbeam::channel::{unbounded, Receiver};
+
+// GAME EVENTS:
+// rapier reports contacts and intersections between colliders. the collector maps the colliders
+// to the game objects owning them and turns the raw events into game events once per step.
+pub type CarId = usize;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ColliderOwner {
+    Ball(usize), // index into the match balls
+    Car(CarId, SelectPart),
+    Arena,
+    Goal(Side),
+    BoostPad(usize),
+    Bumper(usize),  // index into the map's bumpers
+    Trigger(usize), // and into its trigger volumes
+}
+
+// what a bumper or a trigger acts on
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Target {
+    Ball(usize),
+    Car(CarId),
+}
+
+pub type ColliderOwners = HashMap<ColliderHandle, ColliderOwner>;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GameEvent {
+    BallTouched {
+        car: CarId,
+        ball: usize,
+        impulse: f32,
+        point: Option<Vector2<f32>>, // first contact point, in m
+    },
+    BallHitWall {
+        ball: usize,
+        impulse: f32,
+        point: Option<Vector2<f32>>,
+    },
+    GoalScored {
+        side: Side, // the side that scored
+        ball: usize,
+    },
+    CarLanded {
+        car: CarId,
+    },
+    CarHitWall {
+        car: CarId,
+        impulse: f32,
+        point: Option<Vector2<f32>>,
+    },
+    CarsCollided {
+        car1: CarId,
+        car2: CarId,
+        impulse: f32,
+        point: Option<Vector2<f32>>,
+    },
+    Demolition {
+        attacker: CarId,
+        victim: CarId,
+    },
+    BoostPickedUp {
+        car: CarId,
+        pad: usize,
+    },
+    BumperHit {
+        bumper: usize,
+        target: Target,
+        point: Option<Vector2<f32>>,
+    },
+    TriggerEntered {
+        trigger: usize,
+        target: Target,
+    },
+    TriggerLeft {
+        trigger: usize,
+        target: Target,
+    },
+    // from the game modes:
+    KnockedOut {
+        car: CarId,
+    },
+    RoundOver {
+        winner: Option<Side>, // None when the last cars fell together
+    },
+    // from the touch tracker:
+    Shot {
+        car: CarId,
+        ball: usize,
+    },
+    Save {
+        car: CarId,
+        ball: usize,
+    },
+    GoalAttributed {
+        side: Side,
+        ball: usize,
+        scorer: Option<CarId>,
+        assist: Option<CarId>,
+        own_goal: bool,
+    },
+}
+
+pub trait GameEventSubscriber {
+    fn on_event(&mut self, event: &GameEvent);
+}
+
+pub fn dispatch(events: &[GameEvent], subscribers: &mut [&mut dyn GameEventSubscriber]) {
+    for event in events {
+        for subscriber in subscribers.iter_mut() {
+            subscriber.on_event(event);
+        }
+    }
+}
+
+pub struct EventCollector {
+    handler: ChannelEventCollector,
+    contact_recv: Receiver<ContactEvent>,
+    intersection_recv: Receiver<IntersectionEvent>,
+}
+
+impl EventCollector {
+    pub fn new() -> EventCollector {
+        let (contact_send, contact_recv) = unbounded();
+        let (intersection_send, intersection_recv) = unbounded();
+        EventCollector {
+            handler: ChannelEventCollector::new(intersection_send, contact_send),
+            contact_recv,
+            intersection_recv,
+        }
+    }
+
+    // pass this to PhysicsPipeline::step
+    pub fn handler(&self) -> &ChannelEventCollector {
+        &self.handler
+    }
+
+    // call after the step, so the contact impulses of this step are available
+    pub fn collect(&self, owners: &ColliderOwners, narrow_phase: &NarrowPhase) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+
+        while let Ok(event) = self.contact_recv.try_recv() {
+            let (h1, h2) = match event {
+                ContactEvent::Started(h1, h2) => (h1, h2),
+                ContactEvent::Stopped(..) => continue,
+            };
+            let (owner1, owner2) = match (owners.get(&h1), owners.get(&h2)) {
+                (Some(owner1), Some(owner2)) => (*owner1, *owner2),
+                _ => continue,
+            };
+            let impulse = contact_impulse(narrow_phase, h1, h2);
+            let point = contact_point(narrow_phase, h1, h2);
+
+            let event = match sorted(owner1, owner2) {
+                (ColliderOwner::Ball(ball), ColliderOwner::Car(car, _)) => GameEvent::BallTouched {
+                    car,
+                    ball,
+                    impulse,
+                    point,
+                },
+                (ColliderOwner::Ball(ball), ColliderOwner::Arena) => GameEvent::BallHitWall {
+                    ball,
+                    impulse,
+                    point,
+                },
+                (ColliderOwner::Car(car, SelectPart::Wheel), ColliderOwner::Arena) => {
+                    GameEvent::CarLanded { car }
+                }
+                (ColliderOwner::Car(car, SelectPart::Body), ColliderOwner::Arena) => {
+                    GameEvent::CarHitWall {
+                        car,
+                        impulse,
+                        point,
+                    }
+                }
+                (
+                    ColliderOwner::Car(car1, SelectPart::Body),
+                    ColliderOwner::Car(car2, SelectPart::Body),
+                ) if car1 != car2 => GameEvent::CarsCollided {
+                    car1: car1.min(car2),
+                    car2: car1.max(car2),
+                    impulse,
+                    point,
+                },
+                (ColliderOwner::Ball(ball), ColliderOwner::Bumper(bumper)) => {
+                    GameEvent::BumperHit {
+                        bumper,
+                        target: Target::Ball(ball),
+                        point,
+                    }
+                }
+                (ColliderOwner::Car(car, _), ColliderOwner::Bumper(bumper)) => {
+                    GameEvent::BumperHit {
+                        bumper,
+                        target: Target::Car(car),
+                        point,
+                    }
+                }
+                _ => continue,
+            };
+            // both wheels landing in the same step is one landing:
+            if !events.contains(&event) {
+                events.push(event);
+            }
+        }
+
+        while let Ok(event) = self.intersection_recv.try_recv() {
+            let (owner1, owner2) =
+                match (owners.get(&event.collider1), owners.get(&event.collider2)) {
+                    (Some(owner1), Some(owner2)) => (*owner1, *owner2),
+                    _ => continue,
+                };
+            // triggers care about leaving too:
+            let target = match sorted(owner1, owner2) {
+                (ColliderOwner::Ball(ball), ColliderOwner::Trigger(trigger)) => {
+                    Some((Target::Ball(ball), trigger))
+                }
+                (ColliderOwner::Car(car, SelectPart::Body), ColliderOwner::Trigger(trigger)) => {
+                    Some((Target::Car(car), trigger))
+                }
+                _ => None,
+            };
+            if let Some((target, trigger)) = target {
+                events.push(match event.intersecting {
+                    true => GameEvent::TriggerEntered { trigger, target },
+                    false => GameEvent::TriggerLeft { trigger, target },
+                });
+                continue;
+            }
+            if !event.intersecting {
+                continue;
+            }
+            match sorted(owner1, owner2) {
+                (ColliderOwner::Ball(ball), ColliderOwner::Goal(side)) => {
+                    events.push(GameEvent::GoalScored {
+                        side: side.opponent(),
+                        ball,
+                    })
+                }
+                (ColliderOwner::Car(car, SelectPart::Body), ColliderOwner::BoostPad(pad)) => {
+                    events.push(GameEvent::BoostPickedUp { car, pad })
+                }
+                _ => {}
+            }
+        }
+
+        events
+    }
+}
+
+// orders a pair so the match arms above only need one permutation
+fn sorted(a: ColliderOwner, b: ColliderOwner) -> (ColliderOwner, ColliderOwner) {
+    let rank = |owner: &ColliderOwner| match owner {
+        ColliderOwner::Ball(_) => 0,
+        ColliderOwner::Car(..) => 1,
+        ColliderOwner::Arena => 2,
+        ColliderOwner::Goal(_) => 3,
+        ColliderOwner::BoostPad(_) => 4,
+        ColliderOwner::Bumper(_) => 5,
+        ColliderOwner::Trigger(_) => 6,
+    };
+    if rank(&a) <= rank(&b) {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+// total normal impulse of the last step between two colliders
+pub fn contact_impulse(narrow_phase: &NarrowPhase, h1: ColliderHandle, h2: ColliderHandle) -> f32 {
+    narrow_phase.contact_pair(h1, h2).map_or(0.0, |pair| {
+        pair.manifolds
+            .iter()
+            .flat_map(|manifold| manifold.points.iter())
+            .map(|point| point.data.impulse)
+            .sum()
+    })
+}
+
+// world space point of the last step's contact between two colliders
+pub fn contact_point(
+    narrow_phase: &NarrowPhase,
+    h1: ColliderHandle,
+    h2: ColliderHandle,
+) -> Option<Vector2<f32>> {
+    narrow_phase.contact_pair(h1, h2).and_then(|pair| {
+        pair.manifolds
+            .iter()
+            .flat_map(|manifold| manifold.data.solver_contacts.iter())
+            .map(|contact| contact.point.coords)
+            .next()
+    })
+}
+
+// SCORE:
+pub struct Score {
+    pub left: u32,
+    pub right: u32,
+}
+
+impl Score {
+    pub fn new() -> Score {
+        Score { left: 0, right: 0 }
+    }
+
+    pub fn get(&self, side: Side) -> u32 {
+        match side {
+            Side::Left => self.left,
+            Side::Right => self.right,
+        }
+    }
+}
+
+impl GameEventSubscriber for Score {
+    fn on_event(&mut self, event: &GameEvent) {
+        let side = match event {
+            GameEvent::GoalScored { side, .. } => side,
+            GameEvent::RoundOver {
+                winner: Some(side), ..
+            } => side,
+            _ => return,
+        };
+        match side {
+            Side::Left => self.left += 1,
+            Side::Right => self.right += 1,
+        }
+    }
+}
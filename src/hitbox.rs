@@ -0,0 +1,156 @@
+use crate::nalgebra::Vector2;
+use crate::*;
+
+// CAR HITBOXES:
+// body shape of each car preset: a chassis cuboid, a rounded or wedge nose and a cabin on top,
+// all convex. in the body's local frame with the nose towards +x and the roof towards -y,
+// centered on the wheel axles. cars facing the other way get the x-mirrored shape, scaled cars
+// a scaled one.
+const NOSE_ARC_POINTS: usize = 7;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum HitboxPreset {
+    #[default]
+    Octane, // short and tall, rounded nose
+    Dominus,  // long and flat
+    Breakout, // longest and lowest, wedge nose
+}
+
+struct Hitbox {
+    chassis_center: Vector2<f32>,
+    chassis_half_extents: Vector2<f32>,
+    hulls: Vec<Vec<Point<f32>>>, // nose and cabin
+}
+
+impl HitboxPreset {
+    pub const ALL: [HitboxPreset; 3] = [
+        HitboxPreset::Octane,
+        HitboxPreset::Dominus,
+        HitboxPreset::Breakout,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HitboxPreset::Octane => "octane",
+            HitboxPreset::Dominus => "dominus",
+            HitboxPreset::Breakout => "breakout",
+        }
+    }
+
+    fn hitbox(&self) -> Hitbox {
+        match self {
+            HitboxPreset::Octane => Hitbox {
+                chassis_center: vector![-0.5, 0.0],
+                chassis_half_extents: vector![4.3, 0.8],
+                hulls: vec![
+                    rounded_nose(3.8, 1.0, 0.8),
+                    vec![
+                        point![-4.4, -0.8],
+                        point![2.0, -0.8],
+                        point![0.6, -2.1],
+                        point![-3.6, -2.1],
+                    ],
+                ],
+            },
+            HitboxPreset::Dominus => Hitbox {
+                chassis_center: vector![-0.5, 0.0],
+                chassis_half_extents: vector![4.9, 0.7],
+                hulls: vec![
+                    rounded_nose(4.4, 0.8, 0.7),
+                    vec![
+                        point![-4.8, -0.7],
+                        point![1.4, -0.7],
+                        point![0.0, -1.6],
+                        point![-3.8, -1.6],
+                    ],
+                ],
+            },
+            HitboxPreset::Breakout => Hitbox {
+                chassis_center: vector![-1.2, 0.0],
+                chassis_half_extents: vector![4.2, 0.6],
+                hulls: vec![
+                    vec![
+                        point![3.0, -0.6],
+                        point![6.0, 0.3],
+                        point![6.0, 0.6],
+                        point![3.0, 0.6],
+                    ],
+                    vec![
+                        point![-4.6, -0.6],
+                        point![0.8, -0.6],
+                        point![-0.6, -1.4],
+                        point![-3.8, -1.4],
+                    ],
+                ],
+            },
+        }
+    }
+
+    // compound collider shape for a car facing +x (1.0) or -x (-1.0)
+    pub fn shape(&self, facing: f32, scale: f32) -> SharedShape {
+        let hitbox = self.hitbox();
+        let center = transform(Point::from(hitbox.chassis_center), facing, scale);
+        let half = hitbox.chassis_half_extents * scale;
+        let mut shapes = vec![(
+            Isometry::translation(center.x, center.y),
+            SharedShape::cuboid(half.x, half.y),
+        )];
+        for hull in hitbox.hulls.iter() {
+            let points: Vec<Point<f32>> = hull
+                .iter()
+                .map(|point| transform(*point, facing, scale))
+                .collect();
+            shapes.push((
+                Isometry::identity(),
+                SharedShape::convex_hull(&points).expect("hitbox hulls are convex"),
+            ));
+        }
+        SharedShape::compound(shapes)
+    }
+
+    // the same pieces as polygons in the body's local frame, for drawing
+    pub fn outlines(&self, facing: f32, scale: f32) -> Vec<Vec<Point<f32>>> {
+        let hitbox = self.hitbox();
+        let (center, half) = (hitbox.chassis_center, hitbox.chassis_half_extents);
+        let chassis = vec![
+            point![center.x - half.x, center.y - half.y],
+            point![center.x + half.x, center.y - half.y],
+            point![center.x + half.x, center.y + half.y],
+            point![center.x - half.x, center.y + half.y],
+        ];
+        std::iter::once(chassis)
+            .chain(hitbox.hulls)
+            .map(|outline| {
+                outline
+                    .iter()
+                    .map(|point| transform(*point, facing, scale))
+                    .collect()
+            })
+            .collect()
+    }
+
+    // local bounding box as (mins, maxs), the sprite is stretched over it
+    pub fn bounds(&self, facing: f32, scale: f32) -> (Vector2<f32>, Vector2<f32>) {
+        let mut mins = vector![f32::MAX, f32::MAX];
+        let mut maxs = vector![f32::MIN, f32::MIN];
+        for point in self.outlines(facing, scale).iter().flatten() {
+            mins = mins.inf(&point.coords);
+            maxs = maxs.sup(&point.coords);
+        }
+        (mins, maxs)
+    }
+}
+
+// half an ellipse in front of x, as a convex hull together with the chassis end
+fn rounded_nose(x: f32, length: f32, half_height: f32) -> Vec<Point<f32>> {
+    (0..NOSE_ARC_POINTS)
+        .map(|i| {
+            let angle = -PI / 2.0 + PI * i as f32 / (NOSE_ARC_POINTS - 1) as f32;
+            point![x + length * angle.cos(), half_height * angle.sin()]
+        })
+        .collect()
+}
+
+fn transform(point: Point<f32>, facing: f32, scale: f32) -> Point<f32> {
+    point![point.x * facing * scale, point.y * scale]
+}
@@ -0,0 +1,250 @@
+use crate::car::{Car, CarStates};
+use crate::nalgebra::Vector2;
+use crate::*;
+
+// HUD:
+// drawn in screen space after the arena, everything is sized relative to the viewport height so it
+// scales with the window.
+const NOTIFICATION_TIME: f32 = 2.5; // in s on screen
+const NOTIFICATION_FADE: f32 = 0.5; // in s at the end
+const MAX_NOTIFICATIONS: usize = 3;
+const BALL_MARKER_INSET: f32 = 0.04; // fraction of the viewport height from the edge
+
+struct Notification {
+    text: String,
+    color: Color,
+    time_left: f32,
+}
+
+pub struct Hud {
+    notifications: Vec<Notification>, // latest last
+    ball_count: usize,                // goals name their ball when there are several
+}
+
+impl Hud {
+    pub fn new(ball_count: usize) -> Hud {
+        Hud {
+            notifications: Vec::new(),
+            ball_count,
+        }
+    }
+
+    pub fn notify(&mut self, text: String, color: Color) {
+        self.notifications.push(Notification {
+            text,
+            color,
+            time_left: NOTIFICATION_TIME,
+        });
+        if self.notifications.len() > MAX_NOTIFICATIONS {
+            self.notifications.remove(0);
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        for notification in self.notifications.iter_mut() {
+            notification.time_left -= dt;
+        }
+        self.notifications
+            .retain(|notification| notification.time_left > 0.0);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw(
+        &self,
+        score: &Score,
+        match_time_left: f32,
+        car: &Car,
+        ball_pos: Vector2<f32>,
+        camera: &GameCamera,
+        body_set: &RigidBodySet,
+    ) {
+        let view = viewport();
+        let font_size = view.h / 20.0;
+
+        // SCORE AND CLOCK:
+        let center_x = view.x + view.w / 2.0;
+        let box_w = view.w * 0.06;
+        let box_y = view.y + view.h * 0.02;
+        for (side, x) in [
+            (Side::Left, center_x - box_w * 1.6),
+            (Side::Right, center_x + box_w * 0.6),
+        ] {
+            draw_rectangle(x, box_y, box_w, font_size * 1.2, team_color(side));
+            draw_text_centered(
+                &score.get(side).to_string(),
+                x + box_w / 2.0,
+                box_y + font_size,
+                font_size * 1.2,
+                WHITE,
+            );
+        }
+        let seconds = match_time_left.max(0.0).ceil() as u32;
+        let clock = if match_time_left.is_finite() {
+            format!("{}:{:02}", seconds / 60, seconds % 60)
+        } else {
+            "--:--".to_string()
+        };
+        draw_text_centered(&clock, center_x, box_y + font_size * 2.3, font_size, WHITE);
+
+        // NOTIFICATIONS:
+        let mut y = view.y + view.h * 0.3;
+        for notification in self.notifications.iter().rev() {
+            let alpha = (notification.time_left / NOTIFICATION_FADE).min(1.0);
+            draw_text_centered(
+                &notification.text,
+                center_x,
+                y,
+                font_size * 1.3,
+                Color {
+                    a: alpha,
+                    ..notification.color
+                },
+            );
+            y += font_size * 1.5;
+        }
+
+        // BOOST METER AND SPEEDOMETER:
+        let meter_radius = view.h * 0.08;
+        let meter_x = view.x + view.w - meter_radius * 1.4;
+        let meter_y = view.y + view.h - meter_radius * 1.4;
+        let boost_fraction = car.get_boost() / MAX_BOOST;
+        draw_circle(
+            meter_x,
+            meter_y,
+            meter_radius,
+            Color::new(0.0, 0.0, 0.0, 0.5),
+        );
+        draw_circle(
+            meter_x,
+            meter_y,
+            meter_radius * boost_fraction.sqrt(),
+            Color { a: 0.8, ..ORANGE },
+        );
+        draw_circle_lines(meter_x, meter_y, meter_radius, view.h * 0.004, WHITE);
+        draw_text_centered(
+            &format!("{:.0}", car.get_boost()),
+            meter_x,
+            meter_y + font_size * 0.35,
+            font_size * 1.1,
+            WHITE,
+        );
+
+        let state = match car.get_car_state() {
+            CarStates::Ground => "ground",
+            CarStates::Air => "air",
+            CarStates::Wall => "wall",
+        };
+        let speed_color = if car.is_supersonic(body_set) {
+            YELLOW
+        } else {
+            WHITE
+        };
+        let text_x = meter_x - meter_radius * 1.3;
+        draw_text_right(
+            &format!("{:.0} km/h", car.get_speed(body_set) * 3.6),
+            text_x,
+            meter_y,
+            font_size,
+            speed_color,
+        );
+        draw_text_right(
+            state,
+            text_x,
+            meter_y + font_size,
+            font_size * 0.8,
+            LIGHTGRAY,
+        );
+
+        // CAMERA MODE:
+        let camera_mode = if camera.ball_cam {
+            "ball cam"
+        } else {
+            "car cam"
+        };
+        draw_text(
+            camera_mode,
+            view.x + view.h * 0.03,
+            view.y + view.h * 0.97,
+            font_size * 0.8,
+            LIGHTGRAY,
+        );
+
+        // in car cam the ball can leave the screen, point at it from the edge:
+        if !camera.ball_cam {
+            draw_ball_marker(camera.world_to_screen(ball_pos), &view);
+        }
+    }
+}
+
+impl GameEventSubscriber for Hud {
+    fn on_event(&mut self, event: &GameEvent) {
+        match *event {
+            GameEvent::GoalAttributed {
+                side,
+                scorer,
+                assist,
+                own_goal,
+                ball,
+            } => {
+                let mut text = format!("{} scores!", team_name(side));
+                if self.ball_count > 1 {
+                    text = format!("{} scores with ball {}!", team_name(side), ball + 1);
+                }
+                match (scorer, own_goal) {
+                    (Some(scorer), false) => text += &format!("  player {}", scorer + 1),
+                    (Some(scorer), true) => text += &format!("  own goal by player {}", scorer + 1),
+                    (None, _) => {}
+                }
+                if let Some(assist) = assist {
+                    text += &format!(", assist player {}", assist + 1);
+                }
+                self.notify(text, team_color(side));
+            }
+            GameEvent::Save { car, .. } => self.notify(format!("save! player {}", car + 1), WHITE),
+            GameEvent::KnockedOut { car } => {
+                self.notify(format!("player {} is out", car + 1), LIGHTGRAY)
+            }
+            GameEvent::RoundOver { winner } => match winner {
+                Some(side) => self.notify(
+                    format!("{} wins the round!", team_name(side)),
+                    team_color(side),
+                ),
+                None => self.notify("nobody left, round drawn".to_string(), WHITE),
+            },
+            GameEvent::Demolition { attacker, victim } => self.notify(
+                format!("player {} demolished player {}", attacker + 1, victim + 1),
+                RED,
+            ),
+            _ => {}
+        }
+    }
+}
+
+fn draw_ball_marker(ball: Vec2, view: &Viewport) {
+    let inset = view.h * BALL_MARKER_INSET;
+    let (min_x, max_x) = (view.x + inset, view.x + view.w - inset);
+    let (min_y, max_y) = (view.y + inset, view.y + view.h - inset);
+    if ball.x >= min_x && ball.x <= max_x && ball.y >= min_y && ball.y <= max_y {
+        return;
+    }
+    let marker = vec2(ball.x.clamp(min_x, max_x), ball.y.clamp(min_y, max_y));
+    let direction = (ball - marker).normalize_or_zero();
+    let size = inset * 0.6;
+    let side = vec2(-direction.y, direction.x) * size * 0.6;
+    draw_triangle(
+        marker + direction * size,
+        marker - direction * size * 0.5 + side,
+        marker - direction * size * 0.5 - side,
+        WHITE,
+    );
+}
+
+fn draw_text_centered(text: &str, x: f32, y: f32, font_size: f32, color: Color) {
+    let size = measure_text(text, None, font_size as u16, 1.0);
+    draw_text(text, x - size.width / 2.0, y, font_size, color);
+}
+
+fn draw_text_right(text: &str, x: f32, y: f32, font_size: f32, color: Color) {
+    let size = measure_text(text, None, font_size as u16, 1.0);
+    draw_text(text, x - size.width, y, font_size, color);
+}
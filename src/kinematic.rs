@@ -0,0 +1,301 @@
+use crate::nalgebra::Vector2;
+use crate::*;
+
+// KINEMATIC ARENA ELEMENTS:
+// obstacles following a scripted motion. their bodies are kinematic and position based, so rapier
+// works out their velocity from the pose they are given for the end of each step and cars and
+// balls get pushed and carried along like by any moving body.
+const DOOR_MOVE_SHARE: f32 = 0.1; // of the period spent opening and closing each
+const PLATFORM_COLOR: Color = DARKGREEN;
+const BUMPER_COLOR: Color = ORANGE;
+const DOOR_COLOR: Color = LIGHTGRAY;
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Motion {
+    Path {
+        points: Vec<Vector2<f32>>, // loops back to the first one
+        period: f32,
+    },
+    Rotate {
+        center: Vector2<f32>,
+        angvel: f32,
+    },
+    // open for the first half of the period, closed for the second
+    Door {
+        open: Vector2<f32>,
+        closed: Vector2<f32>,
+        period: f32,
+    },
+}
+
+impl Motion {
+    // translation and rotation at a time since the start of the match
+    pub fn pose(&self, time: f32) -> (Vector2<f32>, f32) {
+        match self {
+            Motion::Path { points, period } => {
+                (path_position(points, loop_phase(time, *period)), 0.0)
+            }
+            Motion::Rotate { center, angvel } => (*center, angvel * time),
+            Motion::Door {
+                open,
+                closed,
+                period,
+            } => {
+                let phase = loop_phase(time, *period);
+                let closing = ((phase - 0.5) / DOOR_MOVE_SHARE).clamp(0.0, 1.0);
+                let opening = ((phase - 1.0 + DOOR_MOVE_SHARE) / DOOR_MOVE_SHARE).clamp(0.0, 1.0);
+                let closed_share = closing - opening;
+                (open + (closed - open) * closed_share, 0.0)
+            }
+        }
+    }
+}
+
+// 0..1 through the current period
+fn loop_phase(time: f32, period: f32) -> f32 {
+    (time / period.max(f32::EPSILON)).rem_euclid(1.0)
+}
+
+// point at a share of the closed path's length, at constant speed
+fn path_position(points: &[Vector2<f32>], phase: f32) -> Vector2<f32> {
+    let segments: Vec<(Vector2<f32>, Vector2<f32>)> = points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(from, to)| (*from, *to))
+        .collect();
+    let total: f32 = segments.iter().map(|(from, to)| (to - from).norm()).sum();
+    let mut distance = phase * total;
+    for (from, to) in segments.iter() {
+        let length = (to - from).norm();
+        if distance <= length && length > 0.0 {
+            return from + (to - from) * (distance / length);
+        }
+        distance -= length;
+    }
+    points[0]
+}
+
+pub struct KinematicElement {
+    pub size: Vector2<f32>,
+    pub motion: Motion,
+    pub body_handle: RigidBodyHandle,
+    pub collider_handle: ColliderHandle,
+    color: Color,
+    floor: Option<f32>, // y the element sinks below out of sight, for doors rising out of the floor
+}
+
+impl KinematicElement {
+    pub fn new(
+        size: Vector2<f32>,
+        motion: Motion,
+        color: Color,
+        floor: Option<f32>,
+        body_set: &mut RigidBodySet,
+        coll_set: &mut ColliderSet,
+    ) -> KinematicElement {
+        let (translation, rotation) = motion.pose(0.0);
+        let body = RigidBodyBuilder::new_kinematic_position_based()
+            .translation(translation)
+            .rotation(rotation)
+            .build();
+        let body_handle = body_set.insert(body);
+
+        let collider = ColliderBuilder::cuboid(size.x / 2.0, size.y / 2.0)
+            .collision_groups(groups(&[CollisionLayer::Arena]))
+            .build();
+        let collider_handle = coll_set.insert_with_parent(collider, body_handle, body_set);
+
+        KinematicElement {
+            size,
+            motion,
+            body_handle,
+            collider_handle,
+            color,
+            floor,
+        }
+    }
+
+    // call right before the physics step, with the match time at the end of that step
+    pub fn update(&self, time: f32, body_set: &mut RigidBodySet) {
+        let (translation, rotation) = self.motion.pose(time);
+        body_set[self.body_handle]
+            .set_next_kinematic_position(Isometry::new(translation, rotation));
+    }
+
+    pub fn draw(&self, body_set: &RigidBodySet) {
+        let position = body_set[self.body_handle].position();
+        let half = self.size / 2.0;
+        let corners: Vec<Vec2> = [
+            point![-half.x, -half.y],
+            point![half.x, -half.y],
+            point![half.x, half.y],
+            point![-half.x, half.y],
+        ]
+        .iter()
+        .map(|corner| {
+            let mut corner = (position * corner).coords;
+            if let Some(floor) = self.floor {
+                corner.y = corner.y.min(floor);
+            }
+            let pixel = pos_vec_mtr_to_pxl(corner);
+            vec2(pixel.x, pixel.y)
+        })
+        .collect();
+        draw_triangle(corners[0], corners[1], corners[2], self.color);
+        draw_triangle(corners[0], corners[2], corners[3], self.color);
+    }
+}
+
+// the map's moving entities. doors close the mouth of the side's goal, rising out of the floor or
+// sliding out of the wall, and are left out in modes without goals.
+pub fn spawn_kinematics(
+    map: &MapData,
+    goals: &[Goal],
+    body_set: &mut RigidBodySet,
+    coll_set: &mut ColliderSet,
+) -> Vec<KinematicElement> {
+    let mut elements = Vec::new();
+    for entity in map.entities.iter() {
+        let (size, motion, color, floor) = match entity {
+            MapEntity::Platform {
+                size,
+                period,
+                points,
+            } => (
+                *size,
+                Motion::Path {
+                    points: points.clone(),
+                    period: *period,
+                },
+                PLATFORM_COLOR,
+                None,
+            ),
+            MapEntity::RotatingBumper {
+                size,
+                center,
+                angvel,
+            } => (
+                *size,
+                Motion::Rotate {
+                    center: *center,
+                    angvel: *angvel,
+                },
+                BUMPER_COLOR,
+                None,
+            ),
+            MapEntity::Door { side, period } => {
+                let (center, size) = match goals.iter().find(|goal| goal.side == *side) {
+                    Some(goal) => goal.mouth,
+                    None => continue,
+                };
+                // upright mouths get closed from below, flat ones from the side wall. the open door
+                // hangs through the floor, so only the part above the mouth's bottom gets drawn:
+                let (away, floor) = if size.y >= size.x {
+                    (vector![0.0, size.y], Some(center.y + size.y / 2.0))
+                } else {
+                    match side {
+                        Side::Left => (vector![-size.x, 0.0], None),
+                        Side::Right => (vector![size.x, 0.0], None),
+                    }
+                };
+                (
+                    size,
+                    Motion::Door {
+                        open: center + away,
+                        closed: center,
+                        period: *period,
+                    },
+                    DOOR_COLOR,
+                    floor,
+                )
+            }
+            // static, see triggers:
+            _ => continue,
+        };
+        elements.push(KinematicElement::new(
+            size, motion, color, floor, body_set, coll_set,
+        ));
+    }
+    elements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(actual: Vector2<f32>, expected: Vector2<f32>) {
+        assert!(
+            (actual - expected).norm() < 1e-4,
+            "{:?} is not {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn paths_loop_at_constant_speed() {
+        let motion = Motion::Path {
+            points: vec![vector![0.0, 0.0], vector![10.0, 0.0]],
+            period: 4.0,
+        };
+        for (time, expected) in [
+            (0.0, vector![0.0, 0.0]),
+            (1.0, vector![5.0, 0.0]),
+            (2.0, vector![10.0, 0.0]),
+            (3.0, vector![5.0, 0.0]),
+            (4.0, vector![0.0, 0.0]),
+            (5.0, vector![5.0, 0.0]),
+        ] {
+            let (translation, rotation) = motion.pose(time);
+            assert_near(translation, expected);
+            assert_eq!(rotation, 0.0);
+        }
+    }
+
+    #[test]
+    fn zero_length_paths_stay_put() {
+        let motion = Motion::Path {
+            points: vec![vector![3.0, 4.0], vector![3.0, 4.0]],
+            period: 2.0,
+        };
+        assert_near(motion.pose(0.7).0, vector![3.0, 4.0]);
+
+        let no_period = Motion::Path {
+            points: vec![vector![0.0, 0.0], vector![10.0, 0.0]],
+            period: 0.0,
+        };
+        assert!(no_period.pose(1.3).0.iter().all(|x| x.is_finite()));
+    }
+
+    #[test]
+    fn rotation_keeps_the_center() {
+        let motion = Motion::Rotate {
+            center: vector![5.0, 6.0],
+            angvel: 2.0,
+        };
+        let (translation, rotation) = motion.pose(1.5);
+        assert_near(translation, vector![5.0, 6.0]);
+        assert!((rotation - 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn doors_open_for_the_first_half() {
+        let motion = Motion::Door {
+            open: vector![0.0, 10.0],
+            closed: vector![0.0, 0.0],
+            period: 10.0,
+        };
+        for (time, expected) in [
+            (0.0, vector![0.0, 10.0]),
+            (4.9, vector![0.0, 10.0]),
+            (5.5, vector![0.0, 5.0]), // halfway closed
+            (6.0, vector![0.0, 0.0]),
+            (8.9, vector![0.0, 0.0]),
+            (9.5, vector![0.0, 5.0]), // halfway open again
+            (10.0, vector![0.0, 10.0]),
+            (16.0, vector![0.0, 0.0]),
+        ] {
+            assert_near(motion.pose(time).0, expected);
+        }
+    }
+}
@@ -1,3 +1,6 @@
+// newer toolchains flag our `utils` shadowing the macroquad prelude's, which is intended.
+#![allow(hidden_glob_reexports)]
+
 pub use macroquad::prelude::*;
 pub use rapier2d::prelude::*;
 use std::collections::HashMap;
@@ -10,7 +13,82 @@ mod objects;
 use objects::*;
 
 mod car;
-use car::CarStates;
+use car::{Car, CarSpec, CarStates};
+
+mod assets;
+use assets::*;
+
+mod audio;
+use audio::{watch_audio_device, AudioManager};
+
+mod bumps;
+use bumps::*;
+
+mod camera;
+use camera::GameCamera;
+
+mod collision;
+use collision::*;
+
+mod controls;
+use controls::*;
+
+mod debug;
+use debug::DebugRenderer;
+
+mod drivetrain;
+use drivetrain::*;
+
+mod events;
+use events::*;
+
+mod hitbox;
+use hitbox::*;
+
+mod hud;
+use hud::Hud;
+
+mod kinematic;
+use kinematic::*;
+
+mod map;
+use map::*;
+
+mod menu;
+use menu::*;
+
+mod modes;
+use modes::*;
+
+mod mutators;
+use mutators::*;
+
+mod particles;
+use particles::ParticleSystem;
+
+mod prediction;
+use prediction::*;
+
+mod settings;
+use settings::*;
+
+mod stats;
+use stats::*;
+
+mod teams;
+use teams::*;
+
+mod touch;
+use touch::TouchTracker;
+
+mod triggers;
+use triggers::*;
+
+mod tuning;
+use tuning::*;
+
+mod tyres;
+use tyres::*;
 
 mod utils;
 use crate::utils::*;
@@ -22,19 +100,78 @@ fn window_conf() -> Conf {
         window_width: PIXEL_W,
         window_height: PIXEL_H,
         high_dpi: false,
-        fullscreen: false,
+        // miniquad can only switch to fullscreen at startup on linux and macos:
+        fullscreen: Settings::load().fullscreen,
         // sample_count: 1,
-        window_resizable: false,
+        window_resizable: true,
         ..Default::default()
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum PlayMode {
+    Match,
+    Practice, // no clock and no stats
+}
+
 // MAIN:
 #[macroquad::main(window_conf)]
 async fn main() {
+    watch_audio_device();
+    let assets = Assets::load().await;
+    let mut audio = AudioManager::load().await;
+    let mut settings = Settings::load();
+    let mut settings_screen = SettingsScreen::new();
+
+    // MAIN MENU:
+    loop {
+        clear_background(LETTERBOX_COLOR);
+        if quit_shortcut_pressed() {
+            return;
+        }
+
+        let action = if settings_screen.open {
+            None
+        } else {
+            draw_main_menu()
+        };
+        settings_screen.draw(&mut settings);
+
+        let exit = match action {
+            Some(MenuAction::Play) => {
+                play(PlayMode::Match, &mut settings, &assets, &mut audio).await
+            }
+            Some(MenuAction::Practice) => {
+                play(PlayMode::Practice, &mut settings, &assets, &mut audio).await
+            }
+            Some(MenuAction::Settings) => {
+                settings_screen.open();
+                None
+            }
+            Some(MenuAction::Quit) => return,
+            _ => None,
+        };
+        audio.silence();
+        if exit == Some(MenuAction::Quit) {
+            return;
+        }
+
+        next_frame().await
+    }
+}
+
+// one match or practice session, until the player quits to the menu (or quits the game)
+async fn play(
+    mode: PlayMode,
+    settings: &mut Settings,
+    assets: &Assets,
+    audio: &mut AudioManager,
+) -> Option<MenuAction> {
     //////////////////////////////////////////////////////////
     /* Create Rapier elements necessary for the simulation. */
-    let gravity = vector![0.0, 29.81];
+    let mut tuning = Tuning::load(TUNING_PATH);
+    let mut applied_tuning = Tuning::default();
+    let mut tuning_console = TuningConsole::new();
     // let integration_parameters = IntegrationParameters {
     //     dt: get_frame_time(), // maybe needs to be in the game loop ?
     //     ..Default::default()
@@ -45,7 +182,8 @@ async fn main() {
     let mut narrow_phase = NarrowPhase::new();
     let mut ccd_solver = CCDSolver::new();
     let physics_hooks = ();
-    let event_handler = ();
+    let event_collector = EventCollector::new();
+    let mut owners = ColliderOwners::new();
 
     let mut rigid_body_set = RigidBodySet::new();
     let mut collider_set = ColliderSet::new();
@@ -55,28 +193,32 @@ async fn main() {
     let mut balls: Vec<&FootBall> = Vec::new();
     let mut solids: Vec<&Solid> = Vec::new();
 
-    let ball = FootBall::new(
-        vector![ARENA_WIDTH / 2.0, ARENA_HEIGHT - 10.0],
-        BALL_RADIUS,
-        &mut rigid_body_set,
-        &mut collider_set,
-    );
-    balls.push(&ball);
-
-    // ARENA:
+    // floors, net and goals depend on the game mode:
     let wall_thickness = 2.0;
-
-    let floor = Solid::new_with_contact_event(
-        "floor".to_string(),
-        vector![0.0, ARENA_HEIGHT - wall_thickness],
-        vector![ARENA_WIDTH, wall_thickness],
+    let mode_arena = ModeArena::new(
+        settings.game_mode,
+        wall_thickness,
         &mut rigid_body_set,
         &mut collider_set,
     );
-    solids.push(&floor);
+    solids.extend(mode_arena.floors.iter());
+    solids.extend(mode_arena.extras.iter());
 
+    let ball_storage: Vec<FootBall> = (0..settings.ball_count)
+        .map(|i| {
+            FootBall::new(
+                mode_arena.ball_kickoff_spot(i, settings.ball_count, Side::Left),
+                settings.mutators.ball_radius(),
+                settings.ball_variant,
+                &mut rigid_body_set,
+                &mut collider_set,
+            )
+        })
+        .collect();
+    balls.extend(ball_storage.iter());
+
+    // ARENA:
     let ceiling = Solid::new(
-        "ceiling".to_string(),
         vector![0.0, 0.0],
         vector![ARENA_WIDTH, wall_thickness],
         &mut rigid_body_set,
@@ -85,7 +227,6 @@ async fn main() {
     solids.push(&ceiling);
 
     let wall_left = Solid::new(
-        "wall_left".to_string(),
         vector![0.0, 0.0],
         vector![wall_thickness, ARENA_HEIGHT],
         &mut rigid_body_set,
@@ -94,7 +235,6 @@ async fn main() {
     solids.push(&wall_left);
 
     let wall_right = Solid::new(
-        "wall_right".to_string(),
         vector![ARENA_WIDTH - wall_thickness, 0.0],
         vector![wall_thickness, ARENA_HEIGHT],
         &mut rigid_body_set,
@@ -102,59 +242,216 @@ async fn main() {
     );
     solids.push(&wall_right);
 
+    for s in solids.iter() {
+        owners.insert(s.collider_handle, ColliderOwner::Arena);
+    }
+    for (i, b) in balls.iter().enumerate() {
+        owners.insert(b.collider_handle, ColliderOwner::Ball(i));
+    }
+
+    // GOALS:
+    let goals = &mode_arena.goals;
+    for goal in goals.iter() {
+        owners.insert(goal.sensor_handle, ColliderOwner::Goal(goal.side));
+        for solid in goal.frame.iter() {
+            owners.insert(solid.collider_handle, ColliderOwner::Arena);
+        }
+    }
+
+    // MOVING ELEMENTS: from the map file, on their own clock that only runs with the physics
+    let map = MapData::load(MAP_PATH);
+    let kinematics = spawn_kinematics(&map, goals, &mut rigid_body_set, &mut collider_set);
+    for element in kinematics.iter() {
+        owners.insert(element.collider_handle, ColliderOwner::Arena);
+    }
+    let mut arena_time = 0.0;
+
+    // TRIGGERS AND BUMPERS: also from the map file
+    let mut arena_triggers = ArenaTriggers::new(&map, &mut rigid_body_set, &mut collider_set);
+    arena_triggers.register_colliders(&mut owners);
+
+    // BALL PREDICTION: a world of its own, holding everything the ball can bounce off
+    let mut prediction_solids = solids.clone();
+    prediction_solids.extend(goals.iter().flat_map(|goal| goal.frame.iter()));
+    prediction_solids.extend(arena_triggers.bumpers.iter().map(|bumper| &bumper.solid));
+    let mut ball_predictor = BallPredictor::new(
+        balls[0],
+        &prediction_solids,
+        &kinematics,
+        &rigid_body_set,
+        &collider_set,
+    );
+
+    // BOOST PADS:
+    let mut boost_pads: Vec<BoostPad> = settings
+        .game_mode
+        .boost_pad_spots()
+        .iter()
+        .map(|x| {
+            BoostPad::new(
+                vector![
+                    ARENA_WIDTH * x,
+                    ARENA_HEIGHT - wall_thickness - BOOST_PAD_SIZE
+                ],
+                &mut rigid_body_set,
+                &mut collider_set,
+            )
+        })
+        .collect();
+    for (i, pad) in boost_pads.iter().enumerate() {
+        owners.insert(pad.collider_handle, ColliderOwner::BoostPad(i));
+    }
+
+    let mut score = Score::new();
+    let mut touch_tracker = TouchTracker::new();
+    let mut match_stats = MatchStats::new();
+    let mut match_time_left = settings.mutators.time_limit(settings.match_duration);
+
     // key variable:
     // let mut jump_pressed = false;
 
-    let mut carzz = car::Car::new(
-        vector![ARENA_WIDTH / 4.0, ARENA_HEIGHT - 4.0],
+    // TEAMS: the front car of each side is a local player
+    let team_size = settings.team_size;
+    let spec = CarSpec {
+        drivetrain: Drivetrain {
+            layout: settings.drive_layout,
+            ..Default::default()
+        },
+        hitbox: settings.car_hitbox,
+        scale: settings.mutators.car_scale(),
+    };
+    let mut cars = spawn_teams(
+        team_size,
+        &spec,
         &mut rigid_body_set,
         &mut collider_set,
         &mut joint_set,
     );
+    for car in cars.iter() {
+        car.register_colliders(&mut owners);
+    }
+    let local_players: Vec<Option<usize>> = cars
+        .iter()
+        .map(|car| {
+            cars.iter()
+                .find(|other| other.side == car.side)
+                .filter(|front| front.id == car.id)
+                .map(|_| car.side.index())
+        })
+        .collect();
+    let sides: Vec<Side> = cars.iter().map(|car| car.side).collect();
+    let team_of = |car: CarId| sides[car];
+
+    let mut paused = false;
+    let mut settings_screen = SettingsScreen::new();
+    let mut debug_renderer = DebugRenderer::new();
+    let mut hud = Hud::new(balls.len());
+    let mut particles = ParticleSystem::new();
+
+    let mut camera = GameCamera::new(cars[0].get_position(&rigid_body_set));
+    let mut ball_velocities: Vec<Vector<f32>> = balls
+        .iter()
+        .map(|b| *rigid_body_set[b.body_handle].linvel())
+        .collect();
 
     // GAME LOOP:
     /* Run the game loop, stepping the simulation once per frame. */
     loop {
         clear_background(GRAY);
 
-        // enable quitting with CMD+Q on macos:
-        if let "macos" = std::env::consts::OS {
-            if is_key_down(KeyCode::LeftSuper) && is_key_down(KeyCode::Q) {
-                return; // return from main -> quit
-            }
+        if quit_shortcut_pressed() {
+            return Some(MenuAction::Quit);
+        }
+
+        if is_key_pressed(KeyCode::Escape) && !settings_screen.open {
+            paused = !paused;
+        }
+
+        if FULLSCREEN_SWITCHABLE && is_key_pressed(KeyCode::F11) {
+            settings.fullscreen = !settings.fullscreen;
+            set_fullscreen(settings.fullscreen);
+        }
+
+        if is_key_pressed(KeyCode::F1) {
+            debug_renderer.toggle();
+        }
+
+        if is_key_pressed(KeyCode::F2) {
+            tuning_console.toggle();
+        }
+
+        if is_key_pressed(KeyCode::F3) {
+            settings.ball_trajectory = !settings.ball_trajectory;
+        }
+
+        if is_key_pressed(KeyCode::C) {
+            camera.toggle_ball_cam();
         }
 
         // if is_key_down(KeyCode::Q) {
-        //     carzz.spin(-100.0, &mut rigid_body_set);
+        //     carzz.spin(-tuning.spin_torque, &mut rigid_body_set);
         // }
         // if is_key_down(KeyCode::E) {
-        //     carzz.spin(100.0, &mut rigid_body_set);
+        //     carzz.spin(tuning.spin_torque, &mut rigid_body_set);
         // }
 
-        if is_key_down(KeyCode::Right) && carzz.get_car_state() == CarStates::Ground {
-            carzz.drive(5000.0, &mut rigid_body_set)
-        }
-        if is_key_down(KeyCode::Left) && carzz.get_car_state() == CarStates::Ground {
-            carzz.drive(-5000.0, &mut rigid_body_set)
+        // MATCH CLOCK:
+        let match_running = mode == PlayMode::Practice || match_time_left > 0.0;
+        // forces only get cleared by a step, so no input while the physics stand still:
+        let stepping = match_running && !paused;
+        if stepping && mode == PlayMode::Match {
+            match_time_left -= get_frame_time();
+            if match_time_left <= 0.0 {
+                match_stats.export(&score, STATS_PATH);
+            }
         }
 
-        if is_key_down(KeyCode::Right) && carzz.get_car_state() == CarStates::Air {
-            carzz.spin(100.0, &mut rigid_body_set);
-        }
-        if is_key_down(KeyCode::Left) && carzz.get_car_state() == CarStates::Air {
-            carzz.spin(-100.0, &mut rigid_body_set);
-        }
+        audio.apply_settings(settings);
+        let mut boosting = false;
+        for (carzz, player) in cars.iter_mut().zip(local_players.iter()) {
+            let keys = match player {
+                Some(player) if stepping && !carzz.is_demolished() => &settings.controls[*player],
+                _ => continue,
+            };
+
+            let input = (is_key_down(keys.right) as i32 - is_key_down(keys.left) as i32) as f32;
+            carzz.drive(
+                input,
+                is_key_down(keys.handbrake),
+                &tuning,
+                get_frame_time(),
+                &mut rigid_body_set,
+            );
+            if carzz.get_car_state() == CarStates::Air {
+                carzz.air_control(input, &tuning, get_frame_time(), &mut rigid_body_set);
+            }
+            if is_key_pressed(keys.air_roll) {
+                carzz.air_roll(&mut collider_set);
+            }
 
-        if is_key_down(KeyCode::Up) {
-            carzz.jump(&mut rigid_body_set);
+            if is_key_down(keys.jump) && carzz.jump(tuning.jump_impulse, &mut rigid_body_set) {
+                audio.jump();
+            }
+
+            if is_key_down(keys.boost) && carzz.use_boost(get_frame_time()) {
+                boosting = true;
+                carzz.boost(tuning.boost_force, &mut rigid_body_set);
+                let (nozzle, direction) = carzz.boost_exhaust(&rigid_body_set);
+                particles.emit_exhaust(nozzle, direction, team_color(carzz.side));
+                match_stats.add_boost_used(carzz.id, BOOST_CONSUMPTION * get_frame_time());
+            }
         }
 
-        if is_key_down(KeyCode::Space) {
-            carzz.boost(-300.0, &mut rigid_body_set);
+        // APPLY TUNING:
+        if tuning != applied_tuning {
+            for b in balls.iter() {
+                b.apply_tuning(&tuning, &mut rigid_body_set, &mut collider_set);
+            }
+            for car in cars.iter() {
+                car.apply_tuning(&tuning, &mut rigid_body_set, &mut collider_set);
+            }
+            applied_tuning = tuning.clone();
         }
-        // if is_key_down(KeyCode::Space) && is_key_down(KeyCode::Right) {
-        //     carzz.boost(300.0, Side::Right, &mut rigid_body_set);
-        // }
 
         // UPDATE PHYSICS:
         let integration_parameters = IntegrationParameters {
@@ -163,36 +460,309 @@ async fn main() {
             ..Default::default()
         };
 
-        physics_pipeline.step(
-            &gravity,
-            &integration_parameters,
-            &mut island_manager,
-            &mut broad_phase,
-            &mut narrow_phase,
+        let gravity = settings.mutators.gravity(&tuning);
+        if stepping {
+            arena_time += get_frame_time();
+            for element in kinematics.iter() {
+                element.update(arena_time, &mut rigid_body_set);
+            }
+            arena_triggers.apply_gravity(gravity, &balls, &cars, &mut rigid_body_set);
+            physics_pipeline.step(
+                &gravity,
+                &integration_parameters,
+                &mut island_manager,
+                &mut broad_phase,
+                &mut narrow_phase,
+                &mut rigid_body_set,
+                &mut collider_set,
+                &mut joint_set,
+                &mut ccd_solver,
+                &physics_hooks,
+                event_collector.handler(),
+            );
+            for car in cars.iter_mut().filter(|car| !car.is_demolished()) {
+                car.update_tyres(
+                    &tuning,
+                    get_frame_time(),
+                    &rigid_body_set,
+                    &mut collider_set,
+                    &narrow_phase,
+                );
+            }
+        }
+
+        // GAME EVENTS:
+        let mut events = event_collector.collect(&owners, &narrow_phase);
+        let demolitions = resolve_car_collisions(
+            &events,
+            &mut cars,
+            settings.mutators.respawn_time(),
             &mut rigid_body_set,
             &mut collider_set,
-            &mut joint_set,
-            &mut ccd_solver,
-            &physics_hooks,
-            &event_handler,
         );
+        events.extend(demolitions);
+        let mode_events = mode_arena.events(
+            &balls,
+            &mut cars,
+            &events,
+            &mut rigid_body_set,
+            &mut collider_set,
+            &narrow_phase,
+        );
+        events.extend(mode_events);
+        arena_triggers.handle_events(&events, &balls, &cars, &mut rigid_body_set);
+        // once per ball however many cars hit it, and before shots and saves are judged:
+        let mut hit_balls: Vec<usize> = events
+            .iter()
+            .filter_map(|event| match *event {
+                GameEvent::BallTouched { ball, .. } => Some(ball),
+                _ => None,
+            })
+            .collect();
+        hit_balls.sort_unstable();
+        hit_balls.dedup();
+        for ball in hit_balls {
+            settings.mutators.scale_ball_hit(
+                balls[ball],
+                ball_velocities[ball],
+                &mut rigid_body_set,
+            );
+        }
+        let cars_in_play: Vec<&Car> = cars.iter().filter(|car| !car.is_demolished()).collect();
+        let touch_events = touch_tracker.update(
+            &balls,
+            &cars_in_play,
+            goals,
+            &team_of,
+            gravity,
+            &events,
+            arena_time,
+            &rigid_body_set,
+            &narrow_phase,
+        );
+        events.extend(touch_events);
+        for event in events.iter() {
+            match *event {
+                GameEvent::BoostPickedUp { car, pad } => {
+                    cars[car].add_boost(BOOST_PAD_AMOUNT);
+                    boost_pads[pad].pick_up(&mut collider_set);
+                }
+                GameEvent::BallTouched {
+                    impulse,
+                    point: Some(point),
+                    ..
+                }
+                | GameEvent::BallHitWall {
+                    impulse,
+                    point: Some(point),
+                    ..
+                }
+                | GameEvent::CarHitWall {
+                    impulse,
+                    point: Some(point),
+                    ..
+                }
+                | GameEvent::CarsCollided {
+                    impulse,
+                    point: Some(point),
+                    ..
+                } => particles.emit_sparks(point, impulse),
+                GameEvent::GoalScored { side, ball } => {
+                    particles.emit_goal_explosion(
+                        *rigid_body_set[balls[ball].body_handle].translation(),
+                        team_color(side),
+                    );
+                    // the side that conceded serves next:
+                    balls[ball].reset(
+                        mode_arena.ball_kickoff_spot(ball, balls.len(), side.opponent()),
+                        &mut rigid_body_set,
+                    );
+                    // with more balls in play the others keep going, only the scoring one goes back:
+                    if balls.len() == 1 {
+                        kickoff(&mut cars, team_size, &mut rigid_body_set, &mut collider_set);
+                    }
+                }
+                GameEvent::RoundOver { .. } => {
+                    for (index, b) in balls.iter().enumerate() {
+                        b.reset(
+                            mode_arena.ball_kickoff_spot(index, balls.len(), Side::Left),
+                            &mut rigid_body_set,
+                        );
+                    }
+                    kickoff(&mut cars, team_size, &mut rigid_body_set, &mut collider_set);
+                }
+                _ => {}
+            }
+        }
+        if mode == PlayMode::Match {
+            dispatch(
+                &events,
+                &mut [&mut score, &mut match_stats, &mut hud, &mut *audio],
+            );
+        } else {
+            dispatch(&events, &mut [&mut score, &mut hud, &mut *audio]);
+        }
+        if stepping {
+            audio.update_engine(cars[0].wheel_angvel(&rigid_body_set));
+            audio.set_boosting(boosting);
+        } else {
+            audio.silence();
+        }
+        // notifications wait behind the pause menu:
+        if !paused {
+            hud.update(get_frame_time());
+        }
+
+        if stepping {
+            for pad in boost_pads.iter_mut() {
+                pad.update(get_frame_time(), &mut collider_set);
+            }
+            update_respawns(
+                &mut cars,
+                get_frame_time(),
+                &mut rigid_body_set,
+                &mut collider_set,
+            );
+            for car in cars.iter_mut().filter(|car| !car.is_demolished()) {
+                settings.mutators.update_boost(car, get_frame_time());
+            }
+            for b in balls.iter() {
+                settings.mutators.limit_ball_speed(b, &mut rigid_body_set);
+            }
+        }
+
+        // UPDATE CAMERA:
+        for (b, ball_velocity) in balls.iter().zip(ball_velocities.iter_mut()) {
+            let new_ball_velocity = *rigid_body_set[b.body_handle].linvel();
+            if settings.camera_shake {
+                camera.on_ball_hit((new_ball_velocity - *ball_velocity).norm());
+            }
+            *ball_velocity = new_ball_velocity;
+        }
+
+        // ball cam and the hud marker follow the ball closest to the player:
+        let car_pos = cars[0].get_position(&rigid_body_set);
+        let focus_ball_pos = balls
+            .iter()
+            .map(|b| *rigid_body_set[b.body_handle].translation())
+            .min_by(|a, b| (a - car_pos).norm().total_cmp(&(b - car_pos).norm()))
+            .unwrap();
+        camera.update(car_pos, focus_ball_pos, get_frame_time());
+        camera.set_active();
 
         // UPDATE GRAPHIC ELEMENTS:
         for b in balls.iter() {
-            b.draw(&rigid_body_set);
+            b.draw(&rigid_body_set, assets);
+        }
+        // before the solids, so doors hide in the floor and walls while open:
+        for element in kinematics.iter() {
+            element.draw(&rigid_body_set);
         }
         for s in solids.iter() {
-            s.draw(&rigid_body_set, &collider_set);
+            s.draw(&rigid_body_set, &collider_set, assets);
+        }
+        arena_triggers.draw(&rigid_body_set);
+        for goal in goals.iter() {
+            goal.draw(&rigid_body_set, &collider_set, assets);
+        }
+        for pad in boost_pads.iter() {
+            pad.draw();
         }
 
         // carzzz
-        if carzz.query_wheels_collision(floor.collider_handle, &narrow_phase) {
-            carzz.set_car_state(CarStates::Ground);
+        for carzz in cars.iter_mut() {
+            if carzz.is_demolished() {
+                continue;
+            }
+
+            // moving platforms count as ground too:
+            if mode_arena.is_on_floor(carzz, &narrow_phase)
+                || kinematics.iter().any(|element| {
+                    carzz.query_wheels_collision(element.collider_handle, &narrow_phase)
+                })
+            {
+                carzz.set_car_state(CarStates::Ground);
+            } else if carzz.query_wheels_collision(wall_left.collider_handle, &narrow_phase)
+                || carzz.query_wheels_collision(wall_right.collider_handle, &narrow_phase)
+            {
+                carzz.set_car_state(CarStates::Wall);
+            } else {
+                carzz.set_car_state(CarStates::Air);
+            }
+            if stepping && mode == PlayMode::Match {
+                match_stats.sample_car(carzz, &rigid_body_set, get_frame_time());
+            }
+            if stepping && carzz.get_car_state() == CarStates::Ground {
+                for point in carzz.wheel_ground_points(&rigid_body_set) {
+                    particles.emit_dust(point, carzz.get_velocity(&rigid_body_set));
+                    // spinning wheels throw up a lot more:
+                    if carzz.is_wheelspinning() {
+                        particles.emit_dust(point, carzz.get_velocity(&rigid_body_set));
+                    }
+                }
+            }
+
+            carzz.draw(&rigid_body_set, assets);
+        }
+
+        if settings.ball_trajectory {
+            for b in balls.iter() {
+                ball_predictor
+                    .predict(
+                        b,
+                        &kinematics,
+                        arena_time,
+                        gravity,
+                        TRAJECTORY_HORIZON,
+                        &rigid_body_set,
+                        &collider_set,
+                    )
+                    .draw();
+            }
+        }
+
+        if stepping {
+            particles.update(get_frame_time(), gravity);
+        }
+        particles.draw();
+
+        debug_renderer.draw(&rigid_body_set, &collider_set, &joint_set, &narrow_phase);
+
+        set_default_camera();
+        draw_letterbox(LETTERBOX_COLOR);
+
+        if !match_running {
+            draw_scoreboard(&match_stats, &score, "full time");
+        } else if is_key_down(KeyCode::Tab) && mode == PlayMode::Match {
+            draw_scoreboard(&match_stats, &score, "scoreboard");
         } else {
-            carzz.set_car_state(CarStates::Air);
+            // practice runs without a clock:
+            let time_left = match mode {
+                PlayMode::Match => match_time_left,
+                PlayMode::Practice => f32::INFINITY,
+            };
+            hud.draw(
+                &score,
+                time_left,
+                &cars[0],
+                focus_ball_pos,
+                &camera,
+                &rigid_body_set,
+            );
         }
 
-        carzz.draw(&rigid_body_set);
+        tuning_console.draw(&mut tuning);
+
+        // PAUSE MENU:
+        if paused && !settings_screen.open {
+            match draw_pause_menu() {
+                Some(MenuAction::Resume) => paused = false,
+                Some(MenuAction::Settings) => settings_screen.open(),
+                Some(MenuAction::QuitToMenu) => return Some(MenuAction::QuitToMenu),
+                _ => {}
+            }
+        }
+        settings_screen.draw(settings);
 
         next_frame().await
     }
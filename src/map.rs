@@ -0,0 +1,240 @@
+use crate::nalgebra::Vector2;
+use crate::*;
+
+// MAP DATA:
+// extra arena entities on top of the mode's arena, one `kind = values` line each. positions are
+// centers in m with y pointing down like everywhere else, a missing file is an empty map.
+//   platform = width height period x1 y1 x2 y2 ...  moves through the points and back to the
+//                                                   first one once per period (in s)
+//   rotating_bumper = width height x y angvel        spins around its center, angvel in rad/s
+//   door = side period                               closes the side's goal for half a period
+//   bumper = width height x y strength               kicks what hits it away at strength m/s
+//   ball_booster = width height x y vx vy            adds the velocity to balls passing through
+//   launch_pad = width height x y vx vy              and to cars
+//   portal = width height x1 y1 x2 y2                two ends, entering one comes out of the other
+//   gravity_zone = width height x y gx gy            gravity in m/s² for what is inside
+pub const MAP_PATH: &str = "arena.map";
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum MapEntity {
+    Platform {
+        size: Vector2<f32>,
+        period: f32,
+        points: Vec<Vector2<f32>>,
+    },
+    RotatingBumper {
+        size: Vector2<f32>,
+        center: Vector2<f32>,
+        angvel: f32,
+    },
+    Door {
+        side: Side,
+        period: f32,
+    },
+    Bumper {
+        size: Vector2<f32>,
+        center: Vector2<f32>,
+        strength: f32,
+    },
+    BallBooster {
+        size: Vector2<f32>,
+        center: Vector2<f32>,
+        velocity: Vector2<f32>,
+    },
+    LaunchPad {
+        size: Vector2<f32>,
+        center: Vector2<f32>,
+        velocity: Vector2<f32>,
+    },
+    Portal {
+        size: Vector2<f32>,
+        ends: [Vector2<f32>; 2],
+    },
+    GravityZone {
+        size: Vector2<f32>,
+        center: Vector2<f32>,
+        gravity: Vector2<f32>,
+    },
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct MapData {
+    pub entities: Vec<MapEntity>,
+}
+
+impl MapData {
+    pub fn load(path: &str) -> MapData {
+        std::fs::read_to_string(path)
+            .map(|content| MapData::parse(&content))
+            .unwrap_or_default()
+    }
+
+    // lines with an unknown kind or too few values are skipped
+    pub fn parse(content: &str) -> MapData {
+        let entities = parse_key_values(content)
+            .into_iter()
+            .filter_map(|(kind, values)| parse_entity(kind, values))
+            .collect();
+        MapData { entities }
+    }
+}
+
+fn parse_entity(kind: &str, values: &str) -> Option<MapEntity> {
+    let words: Vec<&str> = values.split_whitespace().collect();
+    let numbers: Vec<f32> = words.iter().filter_map(|word| word.parse().ok()).collect();
+    let all_numbers = numbers.len() == words.len();
+
+    match kind {
+        "platform" if all_numbers && numbers.len() >= 7 && numbers.len() % 2 == 1 => {
+            Some(MapEntity::Platform {
+                size: vector![numbers[0], numbers[1]],
+                period: numbers[2],
+                points: numbers[3..]
+                    .chunks(2)
+                    .map(|point| vector![point[0], point[1]])
+                    .collect(),
+            })
+        }
+        "rotating_bumper" if all_numbers && numbers.len() == 5 => Some(MapEntity::RotatingBumper {
+            size: vector![numbers[0], numbers[1]],
+            center: vector![numbers[2], numbers[3]],
+            angvel: numbers[4],
+        }),
+        "bumper" if all_numbers && numbers.len() == 5 => Some(MapEntity::Bumper {
+            size: vector![numbers[0], numbers[1]],
+            center: vector![numbers[2], numbers[3]],
+            strength: numbers[4],
+        }),
+        "ball_booster" if all_numbers && numbers.len() == 6 => Some(MapEntity::BallBooster {
+            size: vector![numbers[0], numbers[1]],
+            center: vector![numbers[2], numbers[3]],
+            velocity: vector![numbers[4], numbers[5]],
+        }),
+        "launch_pad" if all_numbers && numbers.len() == 6 => Some(MapEntity::LaunchPad {
+            size: vector![numbers[0], numbers[1]],
+            center: vector![numbers[2], numbers[3]],
+            velocity: vector![numbers[4], numbers[5]],
+        }),
+        "portal" if all_numbers && numbers.len() == 6 => Some(MapEntity::Portal {
+            size: vector![numbers[0], numbers[1]],
+            ends: [
+                vector![numbers[2], numbers[3]],
+                vector![numbers[4], numbers[5]],
+            ],
+        }),
+        "gravity_zone" if all_numbers && numbers.len() == 6 => Some(MapEntity::GravityZone {
+            size: vector![numbers[0], numbers[1]],
+            center: vector![numbers[2], numbers[3]],
+            gravity: vector![numbers[4], numbers[5]],
+        }),
+        "door" if words.len() == 2 && numbers.len() == 1 => {
+            let side = match words[0] {
+                "left" => Side::Left,
+                "right" => Side::Right,
+                _ => return None,
+            };
+            Some(MapEntity::Door {
+                side,
+                period: numbers[0],
+            })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_every_kind() {
+        let map = MapData::parse(
+            "platform = 12 1 16 30 20 75 20
+             rotating_bumper = 8 1 52.5 12 1.0
+             door = left 60
+             bumper = 3 3 20 22 15
+             ball_booster = 6 6 52.5 24 0 -12
+             launch_pad = 6 1 32 36.5 0 -20
+             portal = 3 5 14 10 91 10
+             gravity_zone = 14 8 30 8 0 2",
+        );
+        assert_eq!(map.entities.len(), 8);
+        assert_eq!(
+            map.entities[0],
+            MapEntity::Platform {
+                size: vector![12.0, 1.0],
+                period: 16.0,
+                points: vec![vector![30.0, 20.0], vector![75.0, 20.0]],
+            }
+        );
+        assert_eq!(
+            map.entities[6],
+            MapEntity::Portal {
+                size: vector![3.0, 5.0],
+                ends: [vector![14.0, 10.0], vector![91.0, 10.0]],
+            }
+        );
+    }
+
+    #[test]
+    fn platforms_need_whole_points() {
+        // two points at least, and no half point left over:
+        assert!(MapData::parse("platform = 12 1 16 30 20")
+            .entities
+            .is_empty());
+        assert!(MapData::parse("platform = 12 1 16 30 20 75")
+            .entities
+            .is_empty());
+        assert!(MapData::parse("platform = 12 1 16 30 20 75 20 40")
+            .entities
+            .is_empty());
+        assert_eq!(
+            MapData::parse("platform = 12 1 16 30 20 75 20 40 10")
+                .entities
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn doors_need_a_side_word() {
+        assert_eq!(
+            MapData::parse("door = right 30").entities,
+            vec![MapEntity::Door {
+                side: Side::Right,
+                period: 30.0,
+            }]
+        );
+        assert!(MapData::parse("door = middle 30").entities.is_empty());
+        assert!(MapData::parse("door = 30 left").entities.is_empty());
+        assert!(MapData::parse("door = left").entities.is_empty());
+        assert!(MapData::parse("door = left 30 40").entities.is_empty());
+    }
+
+    #[test]
+    fn bad_lines_are_skipped() {
+        let map = MapData::parse(
+            "# a comment
+             trampoline = 1 2 3
+             bumper = 3 3 x 22 15
+             bumper = 3 3 20 22
+             bumper
+             bumper = 3 3 20 22 15 # trailing comment",
+        );
+        assert_eq!(map.entities.len(), 1);
+    }
+
+    #[test]
+    fn sample_map_has_no_bad_lines() {
+        let content = std::fs::read_to_string(MAP_PATH).unwrap();
+        assert_eq!(
+            MapData::parse(&content).entities.len(),
+            parse_key_values(&content).len()
+        );
+    }
+
+    #[test]
+    fn missing_file_is_an_empty_map() {
+        assert_eq!(MapData::load("no/such/arena.map"), MapData::default());
+    }
+}
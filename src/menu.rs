@@ -0,0 +1,297 @@
+use crate::*;
+use macroquad::hash;
+use macroquad::ui::{root_ui, widgets, Id};
+
+// MENUS:
+// main menu before a match, pause menu during one and the settings screen reachable from both.
+// drawn with the macroquad ui on top of whatever is on screen, centered in the viewport.
+const BUTTON_W: f32 = 220.0;
+const BUTTON_H: f32 = 36.0;
+const SETTINGS_W: f32 = 460.0;
+const SETTINGS_H: f32 = 360.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MenuAction {
+    Play,
+    Practice,
+    Settings,
+    Quit,
+    Resume,
+    QuitToMenu,
+}
+
+// stacked buttons in a centered window without title bar, returns the clicked one. entries
+// without an action are shown as plain text, for what isn't there yet
+fn button_menu(id: Id, buttons: &[(&str, Option<MenuAction>)]) -> Option<MenuAction> {
+    let size = vec2(
+        BUTTON_W + 20.0,
+        (BUTTON_H + 6.0) * buttons.len() as f32 + 14.0,
+    );
+    let view = viewport();
+    let position = vec2(
+        view.x + (view.w - size.x) / 2.0,
+        view.y + (view.h - size.y) / 2.0,
+    );
+    root_ui().move_window(id, position);
+
+    let mut clicked = None;
+    widgets::Window::new(id, position, size)
+        .titlebar(false)
+        .movable(false)
+        .ui(&mut root_ui(), |ui| {
+            for (label, action) in buttons {
+                match action {
+                    Some(action) => {
+                        if widgets::Button::new(*label)
+                            .size(vec2(BUTTON_W, BUTTON_H))
+                            .ui(ui)
+                        {
+                            clicked = Some(*action);
+                        }
+                    }
+                    None => widgets::Label::new(*label).ui(ui),
+                }
+            }
+        });
+    clicked
+}
+
+pub fn draw_main_menu() -> Option<MenuAction> {
+    let view = viewport();
+    draw_rectangle(view.x, view.y, view.w, view.h, DARKGRAY);
+    let font_size = view.h / 6.0;
+    let title = "boink";
+    let title_size = measure_text(title, None, font_size as u16, 1.0);
+    draw_text(
+        title,
+        view.x + (view.w - title_size.width) / 2.0,
+        view.y + view.h * 0.22,
+        font_size,
+        WHITE,
+    );
+
+    button_menu(
+        hash!(),
+        &[
+            ("play", Some(MenuAction::Play)),
+            ("practice", Some(MenuAction::Practice)),
+            ("replays (coming soon)", None),
+            ("settings", Some(MenuAction::Settings)),
+            ("quit", Some(MenuAction::Quit)),
+        ],
+    )
+}
+
+pub fn draw_pause_menu() -> Option<MenuAction> {
+    let view = viewport();
+    draw_rectangle(
+        view.x,
+        view.y,
+        view.w,
+        view.h,
+        Color::new(0.0, 0.0, 0.0, 0.5),
+    );
+    button_menu(
+        hash!(),
+        &[
+            ("resume", Some(MenuAction::Resume)),
+            ("settings", Some(MenuAction::Settings)),
+            ("quit to menu", Some(MenuAction::QuitToMenu)),
+        ],
+    )
+}
+
+pub struct SettingsScreen {
+    pub open: bool,
+    rebinding: Option<(usize, usize)>, // local player and control waiting for a key
+    status: String,
+}
+
+impl SettingsScreen {
+    pub fn new() -> SettingsScreen {
+        SettingsScreen {
+            open: false,
+            rebinding: None,
+            status: String::new(),
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+        self.rebinding = None;
+        self.status.clear();
+    }
+
+    // saves when closed with the back button or escape
+    pub fn draw(&mut self, settings: &mut Settings) {
+        if !self.open {
+            return;
+        }
+
+        if let Some((player, control)) = self.rebinding {
+            if let Some(key) = get_last_key_pressed() {
+                if is_bindable(key) {
+                    *settings.controls[player].fields_mut()[control].1 = key;
+                }
+                self.rebinding = None;
+            }
+        } else if is_key_pressed(KeyCode::Escape) {
+            self.close(settings);
+            return;
+        }
+
+        let view = viewport();
+        let id = hash!();
+        let position = vec2(
+            view.x + (view.w - SETTINGS_W) / 2.0,
+            view.y + (view.h - SETTINGS_H) / 2.0,
+        );
+        root_ui().move_window(id, position);
+
+        let mut back = false;
+        let rebinding = &mut self.rebinding;
+        let status = &self.status;
+        widgets::Window::new(id, position, vec2(SETTINGS_W, SETTINGS_H))
+            .label("settings")
+            .movable(false)
+            .ui(&mut root_ui(), |ui| {
+                let tab = ui.tabbar(
+                    hash!(),
+                    vec2(SETTINGS_W - 10.0, 24.0),
+                    &["controls", "audio", "graphics", "match", "mutators"],
+                );
+                ui.separator();
+                match tab {
+                    0 => {
+                        for (player, controls) in settings.controls.iter_mut().enumerate() {
+                            ui.label(None, &format!("player {}", player + 1));
+                            for (control, (name, key)) in controls.fields_mut().iter().enumerate() {
+                                let waiting = *rebinding == Some((player, control));
+                                let binding = if waiting {
+                                    "press a key...".to_string()
+                                } else {
+                                    key_name(**key)
+                                };
+                                ui.label(None, &format!("  {:<6} {}", name, binding));
+                                ui.same_line(SETTINGS_W - 90.0);
+                                if widgets::Button::new("rebind").ui(ui) {
+                                    *rebinding = Some((player, control));
+                                }
+                            }
+                        }
+                    }
+                    1 => {
+                        ui.slider(hash!(), "master", 0.0..1.0, &mut settings.master_volume);
+                        ui.slider(hash!(), "effects", 0.0..1.0, &mut settings.effects_volume);
+                    }
+                    2 => {
+                        // elsewhere it only gets read when the window opens:
+                        let fullscreen = settings.fullscreen;
+                        let label = if FULLSCREEN_SWITCHABLE {
+                            "fullscreen"
+                        } else {
+                            "fullscreen (applies on restart)"
+                        };
+                        ui.checkbox(hash!(), label, &mut settings.fullscreen);
+                        if FULLSCREEN_SWITCHABLE && settings.fullscreen != fullscreen {
+                            set_fullscreen(settings.fullscreen);
+                        }
+                        ui.checkbox(hash!(), "camera shake", &mut settings.camera_shake);
+                        ui.checkbox(hash!(), "ball trajectory", &mut settings.ball_trajectory);
+                    }
+                    3 => {
+                        let names: Vec<&str> =
+                            GameMode::ALL.iter().map(|mode| mode.name()).collect();
+                        let mut selected = GameMode::ALL
+                            .iter()
+                            .position(|mode| *mode == settings.game_mode)
+                            .unwrap_or(0);
+                        ui.combo_box(hash!(), "mode", &names, &mut selected);
+                        settings.game_mode = GameMode::ALL[selected];
+
+                        let mut minutes = settings.match_duration / 60.0;
+                        ui.slider(hash!(), "minutes", 1.0..10.0, &mut minutes);
+                        settings.match_duration = minutes.round() * 60.0;
+
+                        let names: Vec<&str> =
+                            TeamSize::ALL.iter().map(|size| size.name()).collect();
+                        let mut selected = TeamSize::ALL
+                            .iter()
+                            .position(|size| *size == settings.team_size)
+                            .unwrap_or(0);
+                        ui.combo_box(hash!(), "team size", &names, &mut selected);
+                        settings.team_size = TeamSize::ALL[selected];
+
+                        let names: Vec<&str> = HitboxPreset::ALL
+                            .iter()
+                            .map(|hitbox| hitbox.name())
+                            .collect();
+                        let mut selected = HitboxPreset::ALL
+                            .iter()
+                            .position(|hitbox| *hitbox == settings.car_hitbox)
+                            .unwrap_or(0);
+                        ui.combo_box(hash!(), "car", &names, &mut selected);
+                        settings.car_hitbox = HitboxPreset::ALL[selected];
+
+                        let names: Vec<&str> = DriveLayout::ALL
+                            .iter()
+                            .map(|layout| layout.name())
+                            .collect();
+                        let mut selected = DriveLayout::ALL
+                            .iter()
+                            .position(|layout| *layout == settings.drive_layout)
+                            .unwrap_or(0);
+                        ui.combo_box(hash!(), "drive", &names, &mut selected);
+                        settings.drive_layout = DriveLayout::ALL[selected];
+
+                        let names: Vec<&str> = BallVariant::ALL
+                            .iter()
+                            .map(|variant| variant.name())
+                            .collect();
+                        let mut selected = BallVariant::ALL
+                            .iter()
+                            .position(|variant| *variant == settings.ball_variant)
+                            .unwrap_or(0);
+                        ui.combo_box(hash!(), "ball", &names, &mut selected);
+                        settings.ball_variant = BallVariant::ALL[selected];
+
+                        let mut ball_count = settings.ball_count as f32;
+                        ui.slider(hash!(), "balls", 1.0..MAX_BALLS as f32, &mut ball_count);
+                        settings.ball_count = ball_count.round() as usize;
+                        ui.label(None, "match rules apply from the next match");
+                    }
+                    _ => {
+                        for mutator in Mutator::ALL {
+                            let names: Vec<&str> =
+                                mutator.options().iter().map(|(name, _)| *name).collect();
+                            let mut selected = settings.mutators.selected(mutator);
+                            ui.combo_box(
+                                hash!(mutator.name()),
+                                mutator.name(),
+                                &names,
+                                &mut selected,
+                            );
+                            settings.mutators.select(mutator, selected);
+                        }
+                        ui.label(None, "sizes and time limit apply from the next match");
+                    }
+                }
+                ui.separator();
+                if widgets::Button::new("back").ui(ui) {
+                    back = true;
+                }
+                ui.label(None, status);
+            });
+
+        if back {
+            self.close(settings);
+        }
+    }
+
+    fn close(&mut self, settings: &Settings) {
+        match settings.save() {
+            Ok(()) => self.open = false,
+            Err(err) => self.status = format!("could not save settings: {}", err),
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use crate::car::Car;
+use crate::nalgebra::Vector2;
+use crate::*;
+
+// GAME MODES:
+// other ways to score with the same cars and balls. soccer scores in the goals, hoops in raised
+// baskets, volleyball when the ball lands on the opponent's half of the floor and knockout by
+// being the last team with a car left on the platforms.
+const NET_HEIGHT: f32 = 12.0; // in m above the floor
+const NET_THICKNESS: f32 = 1.0; // in m
+const PIT_WIDTH: f32 = 0.15; // share of the arena width between the knockout platforms
+const KNOCKOUT_DEPTH: f32 = 10.0; // in m below the arena, where fallen cars are out
+const SERVE_HEIGHT: f32 = 8.0; // in m above the net
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GameMode {
+    Soccer,
+    Hoops,
+    Volleyball,
+    Knockout,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 4] = [
+        GameMode::Soccer,
+        GameMode::Hoops,
+        GameMode::Volleyball,
+        GameMode::Knockout,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameMode::Soccer => "soccer",
+            GameMode::Hoops => "hoops",
+            GameMode::Volleyball => "volleyball",
+            GameMode::Knockout => "knockout",
+        }
+    }
+
+    // x positions of the boost pads, as shares of the arena width
+    pub fn boost_pad_spots(&self) -> &'static [f32] {
+        match self {
+            GameMode::Soccer | GameMode::Hoops => &[0.2, 0.5, 0.8],
+            GameMode::Volleyball => &[0.15, 0.35, 0.65, 0.85],
+            GameMode::Knockout => &[0.15, 0.85],
+        }
+    }
+}
+
+// the parts of the arena that change with the mode, walls and ceiling are always there
+pub struct ModeArena {
+    pub mode: GameMode,
+    wall_thickness: f32,
+    pub floors: Vec<Solid>, // where cars are on the ground
+    pub extras: Vec<Solid>,
+    pub goals: Vec<Goal>,
+}
+
+impl ModeArena {
+    pub fn new(
+        mode: GameMode,
+        wall_thickness: f32,
+        body_set: &mut RigidBodySet,
+        coll_set: &mut ColliderSet,
+    ) -> ModeArena {
+        let floor_y = ARENA_HEIGHT - wall_thickness;
+        let floors = match mode {
+            GameMode::Knockout => {
+                let platform_w = ARENA_WIDTH * (1.0 - PIT_WIDTH) / 2.0;
+                vec![
+                    Solid::new_with_contact_event(
+                        vector![0.0, floor_y],
+                        vector![platform_w, wall_thickness],
+                        body_set,
+                        coll_set,
+                    ),
+                    Solid::new_with_contact_event(
+                        vector![ARENA_WIDTH - platform_w, floor_y],
+                        vector![platform_w, wall_thickness],
+                        body_set,
+                        coll_set,
+                    ),
+                ]
+            }
+            _ => vec![Solid::new_with_contact_event(
+                vector![0.0, floor_y],
+                vector![ARENA_WIDTH, wall_thickness],
+                body_set,
+                coll_set,
+            )],
+        };
+
+        let extras = match mode {
+            GameMode::Volleyball => vec![Solid::new(
+                vector![(ARENA_WIDTH - NET_THICKNESS) / 2.0, floor_y - NET_HEIGHT],
+                vector![NET_THICKNESS, NET_HEIGHT],
+                body_set,
+                coll_set,
+            )],
+            _ => Vec::new(),
+        };
+
+        let goals = match mode {
+            GameMode::Soccer => [Side::Left, Side::Right]
+                .iter()
+                .map(|side| Goal::new(*side, wall_thickness, body_set, coll_set))
+                .collect(),
+            GameMode::Hoops => [Side::Left, Side::Right]
+                .iter()
+                .map(|side| Goal::new_hoop(*side, wall_thickness, body_set, coll_set))
+                .collect(),
+            GameMode::Volleyball | GameMode::Knockout => Vec::new(),
+        };
+
+        ModeArena {
+            mode,
+            wall_thickness,
+            floors,
+            extras,
+            goals,
+        }
+    }
+
+    // volleyball serves from above the half of the serving side, clear of the net. the other modes
+    // ignore who serves
+    pub fn ball_kickoff_spot(&self, index: usize, count: usize, serving: Side) -> Vector2<f32> {
+        let spot = ball_kickoff_spot(index, count);
+        match self.mode {
+            GameMode::Volleyball => {
+                let x = spot.x / 2.0;
+                vector![
+                    match serving {
+                        Side::Left => x,
+                        Side::Right => ARENA_WIDTH - x,
+                    },
+                    ARENA_HEIGHT - self.wall_thickness - NET_HEIGHT - SERVE_HEIGHT
+                ]
+            }
+            _ => spot,
+        }
+    }
+
+    pub fn is_on_floor(&self, car: &Car, narrow_phase: &NarrowPhase) -> bool {
+        self.floors
+            .iter()
+            .any(|floor| car.query_wheels_collision(floor.collider_handle, narrow_phase))
+    }
+
+    // scoring that doesn't go through the goal sensors. call after the step with the events
+    // collected from it, knocked out cars get taken out of play here.
+    pub fn events(
+        &self,
+        balls: &[&FootBall],
+        cars: &mut [Car],
+        events: &[GameEvent],
+        body_set: &mut RigidBodySet,
+        coll_set: &mut ColliderSet,
+        narrow_phase: &NarrowPhase,
+    ) -> Vec<GameEvent> {
+        let mut new_events = Vec::new();
+        match self.mode {
+            GameMode::Soccer | GameMode::Hoops => {}
+            GameMode::Volleyball => {
+                // the ball landing on one half is a point for the other side, once per step even
+                // when it touches a wall and the floor together:
+                let mut scored: Vec<usize> = Vec::new();
+                for event in events.iter() {
+                    if let GameEvent::BallHitWall { ball, .. } = *event {
+                        if scored.contains(&ball) {
+                            continue;
+                        }
+                        let ball_collider = balls[ball].collider_handle;
+                        let on_floor = self.floors.iter().any(|floor| {
+                            narrow_phase
+                                .contact_pair(ball_collider, floor.collider_handle)
+                                .is_some_and(|pair| pair.has_any_active_contact)
+                        });
+                        if on_floor {
+                            scored.push(ball);
+                            let half = if body_set[balls[ball].body_handle].translation().x
+                                < ARENA_WIDTH / 2.0
+                            {
+                                Side::Left
+                            } else {
+                                Side::Right
+                            };
+                            new_events.push(GameEvent::GoalScored {
+                                side: half.opponent(),
+                                ball,
+                            });
+                        }
+                    }
+                }
+            }
+            GameMode::Knockout => {
+                let out_y = ARENA_HEIGHT + KNOCKOUT_DEPTH;
+                for car in cars.iter_mut().filter(|car| !car.is_demolished()) {
+                    if car.get_position(body_set).y > out_y {
+                        // out until the next round:
+                        car.demolish(f32::INFINITY, body_set, coll_set);
+                        new_events.push(GameEvent::KnockedOut { car: car.id });
+                    }
+                }
+                if !new_events.is_empty() {
+                    // demolished cars waiting to respawn are still in the round:
+                    let standing = |side: Side| {
+                        cars.iter()
+                            .any(|car| car.side == side && !car.is_knocked_out())
+                    };
+                    match (standing(Side::Left), standing(Side::Right)) {
+                        (true, false) => new_events.push(GameEvent::RoundOver {
+                            winner: Some(Side::Left),
+                        }),
+                        (false, true) => new_events.push(GameEvent::RoundOver {
+                            winner: Some(Side::Right),
+                        }),
+                        (false, false) => new_events.push(GameEvent::RoundOver { winner: None }),
+                        (true, true) => {}
+                    }
+                }
+
+                // balls falling through the pit come back:
+                for (index, ball) in balls.iter().enumerate() {
+                    if body_set[ball.body_handle].translation().y > out_y {
+                        ball.reset(
+                            self.ball_kickoff_spot(index, balls.len(), Side::Left),
+                            body_set,
+                        );
+                    }
+                }
+            }
+        }
+        new_events
+    }
+}
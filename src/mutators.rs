@@ -0,0 +1,186 @@
+use crate::car::Car;
+use crate::*;
+
+// MUTATORS:
+// match modifiers on top of the rules and the tuned physics. each mutator is a list of named
+// options with the value they stand for, the first option is the unmodified game. gravity, ball
+// speed, boost and respawn time apply right away, the others from the next match.
+const BALL_MAX_SPEED: f32 = 60.0; // in m/s, times the ball speed factor. the default has no cap
+const SLOW_BOOST_RECHARGE: f32 = 5.0; // per second
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Mutator {
+    Gravity,
+    BallSize,
+    BallSpeed,
+    Boost,
+    CarSize,
+    RespawnTime,
+    TimeLimit,
+}
+
+impl Mutator {
+    pub const ALL: [Mutator; 7] = [
+        Mutator::Gravity,
+        Mutator::BallSize,
+        Mutator::BallSpeed,
+        Mutator::Boost,
+        Mutator::CarSize,
+        Mutator::RespawnTime,
+        Mutator::TimeLimit,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Mutator::Gravity => "gravity",
+            Mutator::BallSize => "ball size",
+            Mutator::BallSpeed => "ball speed",
+            Mutator::Boost => "boost",
+            Mutator::CarSize => "car size",
+            Mutator::RespawnTime => "respawn time",
+            Mutator::TimeLimit => "time limit",
+        }
+    }
+
+    // the values are factors, except for boost (recharge per second, infinite ones fill or
+    // empty the tank at once) and respawn time (in s)
+    pub fn options(&self) -> &'static [(&'static str, f32)] {
+        match self {
+            Mutator::Gravity => &[
+                ("default", 1.0),
+                ("low", 0.4),
+                ("high", 1.75),
+                ("reversed", -1.0),
+            ],
+            Mutator::BallSize => &[
+                ("default", 1.0),
+                ("small", 0.7),
+                ("large", 1.4),
+                ("huge", 2.0),
+            ],
+            Mutator::BallSpeed => &[
+                ("default", 1.0),
+                ("slow", 0.5),
+                ("fast", 1.5),
+                ("super fast", 2.5),
+            ],
+            Mutator::Boost => &[
+                ("default", 0.0),
+                ("unlimited", f32::INFINITY),
+                ("none", f32::NEG_INFINITY),
+                ("slow recharge", SLOW_BOOST_RECHARGE),
+            ],
+            Mutator::CarSize => &[("default", 1.0), ("small", 0.75), ("large", 1.3)],
+            Mutator::RespawnTime => &[("default", RESPAWN_TIME), ("fast", 1.0), ("slow", 6.0)],
+            Mutator::TimeLimit => &[
+                ("default", 1.0),
+                ("half", 0.5),
+                ("double", 2.0),
+                ("unlimited", f32::INFINITY),
+            ],
+        }
+    }
+
+    fn index(&self) -> usize {
+        Mutator::ALL
+            .iter()
+            .position(|mutator| mutator == self)
+            .unwrap()
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Mutators {
+    selected: [usize; Mutator::ALL.len()], // option per mutator, in Mutator::ALL order
+}
+
+impl Mutators {
+    pub fn selected(&self, mutator: Mutator) -> usize {
+        self.selected[mutator.index()]
+    }
+
+    pub fn select(&mut self, mutator: Mutator, option: usize) {
+        self.selected[mutator.index()] = option.min(mutator.options().len() - 1);
+    }
+
+    pub fn option_name(&self, mutator: Mutator) -> &'static str {
+        mutator.options()[self.selected(mutator)].0
+    }
+
+    // unknown option names keep the current one
+    pub fn select_by_name(&mut self, mutator: Mutator, name: &str) {
+        if let Some(option) = mutator
+            .options()
+            .iter()
+            .position(|(option, _)| *option == name)
+        {
+            self.select(mutator, option);
+        }
+    }
+
+    fn value(&self, mutator: Mutator) -> f32 {
+        mutator.options()[self.selected(mutator)].1
+    }
+
+    pub fn gravity(&self, tuning: &Tuning) -> Vector<f32> {
+        tuning.gravity_vector() * self.value(Mutator::Gravity)
+    }
+
+    pub fn ball_radius(&self) -> f32 {
+        BALL_RADIUS * self.value(Mutator::BallSize)
+    }
+
+    pub fn car_scale(&self) -> f32 {
+        self.value(Mutator::CarSize)
+    }
+
+    pub fn respawn_time(&self) -> f32 {
+        self.value(Mutator::RespawnTime)
+    }
+
+    pub fn time_limit(&self, match_duration: f32) -> f32 {
+        match_duration * self.value(Mutator::TimeLimit)
+    }
+
+    // recharges, refills or drains the tank, call every frame the physics step
+    pub fn update_boost(&self, car: &mut Car, dt: f32) {
+        let recharge = self.value(Mutator::Boost);
+        if recharge.is_infinite() {
+            car.set_boost(if recharge > 0.0 { MAX_BOOST } else { 0.0 });
+        } else {
+            car.add_boost(recharge * dt);
+        }
+    }
+
+    // scales the velocity change of a car hit, velocity_before is the ball's from the last frame
+    pub fn scale_ball_hit(
+        &self,
+        ball: &FootBall,
+        velocity_before: Vector<f32>,
+        body_set: &mut RigidBodySet,
+    ) {
+        let factor = self.value(Mutator::BallSpeed);
+        if factor == 1.0 {
+            return;
+        }
+        let body = &mut body_set[ball.body_handle];
+        let velocity = *body.linvel();
+        body.set_linvel(
+            velocity_before + (velocity - velocity_before) * factor,
+            true,
+        );
+    }
+
+    pub fn limit_ball_speed(&self, ball: &FootBall, body_set: &mut RigidBodySet) {
+        let factor = self.value(Mutator::BallSpeed);
+        if factor == 1.0 {
+            return;
+        }
+        let max_speed = BALL_MAX_SPEED * factor;
+        let body = &mut body_set[ball.body_handle];
+        let velocity = *body.linvel();
+        if velocity.norm() > max_speed {
+            body.set_linvel(velocity.normalize() * max_speed, true);
+        }
+    }
+}
@@ -7,11 +7,85 @@ fn corner_to_center(corner: Vector2<f32>, size: Vector2<f32>) -> Vector2<f32> {
     vector![x, y]
 }
 
+// BALL VARIANTS:
+// factors on the tuned ball values, so the tuning console still moves every variant
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BallVariant {
+    Standard,
+    Heavy,
+    Bouncy,
+    Cube,
+    Puck, // flat and floaty
+}
+
+impl BallVariant {
+    pub const ALL: [BallVariant; 5] = [
+        BallVariant::Standard,
+        BallVariant::Heavy,
+        BallVariant::Bouncy,
+        BallVariant::Cube,
+        BallVariant::Puck,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BallVariant::Standard => "standard",
+            BallVariant::Heavy => "heavy",
+            BallVariant::Bouncy => "bouncy",
+            BallVariant::Cube => "cube",
+            BallVariant::Puck => "puck",
+        }
+    }
+
+    fn density_factor(&self) -> f32 {
+        match self {
+            BallVariant::Heavy => 3.0,
+            BallVariant::Bouncy => 0.8,
+            BallVariant::Puck => 0.6,
+            _ => 1.0,
+        }
+    }
+
+    fn restitution_factor(&self) -> f32 {
+        match self {
+            BallVariant::Heavy => 0.6,
+            BallVariant::Bouncy => 1.4,
+            BallVariant::Cube => 0.8,
+            BallVariant::Puck => 0.7,
+            BallVariant::Standard => 1.0,
+        }
+    }
+
+    fn gravity_factor(&self) -> f32 {
+        match self {
+            BallVariant::Heavy => 1.5,
+            BallVariant::Puck => 0.25,
+            _ => 1.0,
+        }
+    }
+
+    // None for the round variants
+    fn half_extents(&self, radius: f32) -> Option<Vector2<f32>> {
+        match self {
+            BallVariant::Cube => Some(vector![radius * 0.85, radius * 0.85]),
+            BallVariant::Puck => Some(vector![radius * 1.2, radius * 0.4]),
+            _ => None,
+        }
+    }
+}
+
+// kickoff position of ball `index` out of `count`, spread evenly across the middle of the arena
+pub fn ball_kickoff_spot(index: usize, count: usize) -> Vector2<f32> {
+    vector![
+        ARENA_WIDTH * (index + 1) as f32 / (count + 1) as f32,
+        ARENA_HEIGHT - 10.0
+    ]
+}
+
 #[derive(Debug)]
 pub struct FootBall {
-    pub pos: Vector2<f32>,
     pub radius: f32,
-    pub rot: f32,
+    pub variant: BallVariant,
     pub body_handle: RigidBodyHandle,
     pub collider_handle: ColliderHandle,
 }
@@ -20,35 +94,94 @@ impl FootBall {
     pub fn new(
         pos: Vector2<f32>,
         radius: f32,
+        variant: BallVariant,
         body_set: &mut RigidBodySet,
         coll_set: &mut ColliderSet,
     ) -> FootBall {
         let body = RigidBodyBuilder::new_dynamic()
             .translation(pos)
             .rotation(0.0)
-            .gravity_scale(BALL_GRAV_SCALE)
+            .gravity_scale(BALL_GRAV_SCALE * variant.gravity_factor())
             .build();
         let ball_handle = body_set.insert(body);
 
-        let collider = ColliderBuilder::ball(radius)
-            .restitution(BALL_RESTITUTION)
-            .collision_groups(InteractionGroups::new(0b1000, 0b1101))
-            .density(BALL_DENSITY)
-            .build();
+        let collider = match variant.half_extents(radius) {
+            Some(half) => ColliderBuilder::cuboid(half.x, half.y),
+            None => ColliderBuilder::ball(radius),
+        }
+        .restitution(BALL_RESTITUTION * variant.restitution_factor())
+        .collision_groups(groups(&[CollisionLayer::Ball]))
+        .active_events(ActiveEvents::CONTACT_EVENTS)
+        .density(BALL_DENSITY * variant.density_factor())
+        .build();
 
         let ball_collider_handle = coll_set.insert_with_parent(collider, ball_handle, body_set);
 
         FootBall {
-            pos,
-            rot: 0.0,
             radius,
+            variant,
             body_handle: ball_handle,
             collider_handle: ball_collider_handle,
         }
     }
 
-    pub fn draw(&self, body_set: &RigidBodySet) {
+    pub fn reset(&self, pos: Vector2<f32>, body_set: &mut RigidBodySet) {
+        let body = body_set.get_mut(self.body_handle).unwrap();
+        body.set_translation(pos, true);
+        body.set_rotation(0.0, true);
+        body.set_linvel(vector![0.0, 0.0], true);
+        body.set_angvel(0.0, true);
+    }
+
+    pub fn apply_tuning(
+        &self,
+        tuning: &Tuning,
+        body_set: &mut RigidBodySet,
+        coll_set: &mut ColliderSet,
+    ) {
+        body_set
+            .get_mut(self.body_handle)
+            .unwrap()
+            .set_gravity_scale(tuning.ball_grav_scale * self.variant.gravity_factor(), true);
+        coll_set
+            .get_mut(self.collider_handle)
+            .unwrap()
+            .set_restitution(tuning.ball_restitution * self.variant.restitution_factor());
+    }
+
+    pub fn draw(&self, body_set: &RigidBodySet, assets: &Assets) {
         let translation = body_set[self.body_handle].translation();
+        if let Some(half) = self.variant.half_extents(self.radius) {
+            let position = body_set[self.body_handle].position();
+            let corners: Vec<Vec2> = [
+                point![-half.x, -half.y],
+                point![half.x, -half.y],
+                point![half.x, half.y],
+                point![-half.x, half.y],
+            ]
+            .iter()
+            .map(|corner| {
+                let pixel = pos_vec_mtr_to_pxl((position * corner).coords);
+                vec2(pixel.x, pixel.y)
+            })
+            .collect();
+            draw_triangle(corners[0], corners[1], corners[2], YELLOW);
+            draw_triangle(corners[0], corners[2], corners[3], YELLOW);
+            return;
+        }
+        if let Some(texture) = assets.ball {
+            let diameter = self.radius * 2.0;
+            draw_sprite_center(
+                texture,
+                None,
+                *translation,
+                vector![diameter, diameter],
+                body_set[self.body_handle].rotation().angle(),
+                false,
+            );
+            return;
+        }
+
         let rotation = body_set[self.body_handle].rotation().angle().to_degrees();
         draw_poly(
             pos_x_mtr_to_pxl(translation.x),
@@ -58,19 +191,10 @@ impl FootBall {
             rotation,
             YELLOW,
         );
-
-        draw_circle(
-            pos_x_mtr_to_pxl(translation.x),
-            pos_y_mtr_to_pxl(translation.y),
-            size_mtr_to_pxl(0.3),
-            BLUE,
-        );
     }
 }
 
 pub struct Solid {
-    pub name: String,
-    pub pos: Vector2<f32>,
     pub size: Vector2<f32>,
     pub body_handle: RigidBodyHandle,
     pub collider_handle: ColliderHandle,
@@ -78,7 +202,6 @@ pub struct Solid {
 
 impl Solid {
     pub fn new(
-        name: String,
         pos: Vector2<f32>,
         size: Vector2<f32>,
         body_set: &mut RigidBodySet,
@@ -90,19 +213,18 @@ impl Solid {
             .build();
         let solid_handle = body_set.insert(body);
 
-        let collider = ColliderBuilder::cuboid(size.x / 2.0, size.y / 2.0).build();
+        let collider = ColliderBuilder::cuboid(size.x / 2.0, size.y / 2.0)
+            .collision_groups(groups(&[CollisionLayer::Arena]))
+            .build();
         let solid_collider_handle = coll_set.insert_with_parent(collider, solid_handle, body_set);
 
         Solid {
-            name,
-            pos,
             size,
             body_handle: solid_handle,
             collider_handle: solid_collider_handle,
         }
     }
     pub fn new_with_contact_event(
-        name: String,
         pos: Vector2<f32>,
         size: Vector2<f32>,
         body_set: &mut RigidBodySet,
@@ -115,22 +237,31 @@ impl Solid {
         let solid_handle = body_set.insert(body);
 
         let collider = ColliderBuilder::cuboid(size.x / 2.0, size.y / 2.0)
+            .collision_groups(groups(&[CollisionLayer::Arena]))
             .active_events(ActiveEvents::CONTACT_EVENTS)
             .build();
         let solid_collider_handle = coll_set.insert_with_parent(collider, solid_handle, body_set);
 
         Solid {
-            name,
-            pos,
             size,
             body_handle: solid_handle,
             collider_handle: solid_collider_handle,
         }
     }
-    pub fn draw(&self, body_set: &RigidBodySet, _coll_set: &ColliderSet) {
+    pub fn draw(&self, body_set: &RigidBodySet, _coll_set: &ColliderSet, assets: &Assets) {
         let translation = body_set[self.body_handle].translation();
         let corner_x = translation.x - self.size.x / 2.0;
         let corner_y = translation.y - self.size.y / 2.0;
+        if let Some(texture) = assets.arena_tile {
+            draw_tiled(
+                texture,
+                vector![corner_x, corner_y],
+                self.size,
+                ARENA_TILE_SIZE,
+            );
+            return;
+        }
+
         draw_rectangle(
             pos_x_mtr_to_pxl(corner_x),
             pos_y_mtr_to_pxl(corner_y),
@@ -138,12 +269,226 @@ impl Solid {
             size_mtr_to_pxl(self.size.y),
             GREEN,
         );
+    }
+}
+
+pub struct Goal {
+    pub side: Side, // the side defending this goal
+    pub pos: Vector2<f32>,
+    pub size: Vector2<f32>,
+    pub sensor_handle: ColliderHandle,
+    pub frame: Vec<Solid>, // crossbar of a goal, rim and bottom of a hoop
+    pub mouth: (Vector2<f32>, Vector2<f32>), // center and size of the opening the ball goes in by
+}
 
+impl Goal {
+    pub fn new(
+        side: Side,
+        wall_thickness: f32,
+        body_set: &mut RigidBodySet,
+        coll_set: &mut ColliderSet,
+    ) -> Goal {
+        let pos = match side {
+            Side::Left => vector![wall_thickness, ARENA_HEIGHT - wall_thickness - GOAL_HEIGHT],
+            Side::Right => vector![
+                ARENA_WIDTH - wall_thickness - GOAL_DEPTH,
+                ARENA_HEIGHT - wall_thickness - GOAL_HEIGHT
+            ],
+        };
+        let size = vector![GOAL_DEPTH, GOAL_HEIGHT];
+        let line_x = match side {
+            Side::Left => pos.x + GOAL_DEPTH,
+            Side::Right => pos.x,
+        };
+
+        // the sensor only covers the back of the goal, so the ball has to cross the line:
+        let sensor_x = match side {
+            Side::Left => pos.x,
+            Side::Right => pos.x + GOAL_DEPTH - GOAL_SENSOR_DEPTH,
+        };
+        let sensor_size = vector![GOAL_SENSOR_DEPTH, GOAL_HEIGHT];
+        let body = RigidBodyBuilder::new_static()
+            .translation(corner_to_center(vector![sensor_x, pos.y], sensor_size))
+            .build();
+        let sensor_body_handle = body_set.insert(body);
+        let collider = ColliderBuilder::cuboid(sensor_size.x / 2.0, sensor_size.y / 2.0)
+            .sensor(true)
+            .collision_groups(groups(&[CollisionLayer::GoalSensor]))
+            .active_events(ActiveEvents::INTERSECTION_EVENTS)
+            .build();
+        let sensor_handle = coll_set.insert_with_parent(collider, sensor_body_handle, body_set);
+
+        let crossbar = Solid::new(
+            vector![pos.x, pos.y - CROSSBAR_THICKNESS],
+            vector![GOAL_DEPTH, CROSSBAR_THICKNESS],
+            body_set,
+            coll_set,
+        );
+
+        Goal {
+            side,
+            pos,
+            size,
+            sensor_handle,
+            frame: vec![crossbar],
+            mouth: (
+                vector![line_x, pos.y + GOAL_HEIGHT / 2.0],
+                vector![CROSSBAR_THICKNESS, GOAL_HEIGHT],
+            ),
+        }
+    }
+
+    // a raised basket against the side wall, the ball has to drop in from above
+    pub fn new_hoop(
+        side: Side,
+        wall_thickness: f32,
+        body_set: &mut RigidBodySet,
+        coll_set: &mut ColliderSet,
+    ) -> Goal {
+        let bottom = ARENA_HEIGHT - wall_thickness - HOOP_HEIGHT;
+        let pos = match side {
+            Side::Left => vector![wall_thickness, bottom - HOOP_DEPTH],
+            Side::Right => vector![
+                ARENA_WIDTH - wall_thickness - HOOP_WIDTH,
+                bottom - HOOP_DEPTH
+            ],
+        };
+        let size = vector![HOOP_WIDTH, HOOP_DEPTH];
+
+        // the sensor lies on the bottom of the basket:
+        let sensor_size = vector![HOOP_WIDTH, GOAL_SENSOR_DEPTH];
+        let body = RigidBodyBuilder::new_static()
+            .translation(corner_to_center(
+                vector![pos.x, bottom - GOAL_SENSOR_DEPTH],
+                sensor_size,
+            ))
+            .build();
+        let sensor_body_handle = body_set.insert(body);
+        let collider = ColliderBuilder::cuboid(sensor_size.x / 2.0, sensor_size.y / 2.0)
+            .sensor(true)
+            .collision_groups(groups(&[CollisionLayer::GoalSensor]))
+            .active_events(ActiveEvents::INTERSECTION_EVENTS)
+            .build();
+        let sensor_handle = coll_set.insert_with_parent(collider, sensor_body_handle, body_set);
+
+        let rim_x = match side {
+            Side::Left => pos.x + HOOP_WIDTH,
+            Side::Right => pos.x - CROSSBAR_THICKNESS,
+        };
+        let rim = Solid::new(
+            vector![rim_x, pos.y],
+            vector![CROSSBAR_THICKNESS, HOOP_DEPTH],
+            body_set,
+            coll_set,
+        );
+        let basket = Solid::new(
+            vector![pos.x.min(rim_x), bottom],
+            vector![HOOP_WIDTH + CROSSBAR_THICKNESS, CROSSBAR_THICKNESS],
+            body_set,
+            coll_set,
+        );
+
+        Goal {
+            side,
+            pos,
+            size,
+            sensor_handle,
+            frame: vec![rim, basket],
+            mouth: (
+                vector![pos.x + HOOP_WIDTH / 2.0, pos.y],
+                vector![HOOP_WIDTH, CROSSBAR_THICKNESS],
+            ),
+        }
+    }
+
+    pub fn draw(&self, body_set: &RigidBodySet, coll_set: &ColliderSet, assets: &Assets) {
+        draw_rectangle(
+            pos_x_mtr_to_pxl(self.pos.x),
+            pos_y_mtr_to_pxl(self.pos.y),
+            size_mtr_to_pxl(self.size.x),
+            size_mtr_to_pxl(self.size.y),
+            Color {
+                a: 0.25,
+                ..team_color(self.side)
+            },
+        );
+        // the goal line runs along the mouth, upright in a goal and flat across a hoop:
+        let (center, size) = self.mouth;
+        let half = if size.y >= size.x {
+            vector![0.0, size.y / 2.0]
+        } else {
+            vector![size.x / 2.0, 0.0]
+        };
+        draw_line(
+            pos_x_mtr_to_pxl(center.x - half.x),
+            pos_y_mtr_to_pxl(center.y - half.y),
+            pos_x_mtr_to_pxl(center.x + half.x),
+            pos_y_mtr_to_pxl(center.y + half.y),
+            size_mtr_to_pxl(0.2),
+            WHITE,
+        );
+        for solid in self.frame.iter() {
+            solid.draw(body_set, coll_set, assets);
+        }
+    }
+}
+
+pub struct BoostPad {
+    pub pos: Vector2<f32>,
+    pub collider_handle: ColliderHandle,
+    cooldown: f32, // in s, active when 0
+}
+
+impl BoostPad {
+    pub fn new(
+        pos: Vector2<f32>,
+        body_set: &mut RigidBodySet,
+        coll_set: &mut ColliderSet,
+    ) -> BoostPad {
+        let body = RigidBodyBuilder::new_static().translation(pos).build();
+        let body_handle = body_set.insert(body);
+        let collider = ColliderBuilder::cuboid(BOOST_PAD_SIZE / 2.0, BOOST_PAD_SIZE / 2.0)
+            .sensor(true)
+            .collision_groups(groups(&[CollisionLayer::BoostPad]))
+            .active_events(ActiveEvents::INTERSECTION_EVENTS)
+            .build();
+        let collider_handle = coll_set.insert_with_parent(collider, body_handle, body_set);
+
+        BoostPad {
+            pos,
+            collider_handle,
+            cooldown: 0.0,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.cooldown <= 0.0
+    }
+
+    // an inactive pad leaves all layers, so it can't generate pickups until it's back
+    pub fn pick_up(&mut self, coll_set: &mut ColliderSet) {
+        self.cooldown = BOOST_PAD_COOLDOWN;
+        coll_set[self.collider_handle].set_collision_groups(InteractionGroups::none());
+    }
+
+    pub fn update(&mut self, dt: f32, coll_set: &mut ColliderSet) {
+        if self.is_active() {
+            return;
+        }
+        self.cooldown -= dt;
+        if self.is_active() {
+            coll_set[self.collider_handle]
+                .set_collision_groups(groups(&[CollisionLayer::BoostPad]));
+        }
+    }
+
+    pub fn draw(&self) {
+        let color = if self.is_active() { GOLD } else { DARKGRAY };
         draw_circle(
-            pos_x_mtr_to_pxl(translation.x),
-            pos_y_mtr_to_pxl(translation.y),
-            size_mtr_to_pxl(0.3),
-            BLUE,
+            pos_x_mtr_to_pxl(self.pos.x),
+            pos_y_mtr_to_pxl(self.pos.y),
+            size_mtr_to_pxl(BOOST_PAD_SIZE / 2.0),
+            color,
         );
     }
 }
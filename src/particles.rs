@@ -0,0 +1,137 @@
+use crate::nalgebra::Vector2;
+use crate::*;
+
+// PARTICLES:
+// purely visual, simulated on their own in m and never touching the rapier sets.
+const MAX_PARTICLES: usize = 2000;
+const EXHAUST_SPEED: f32 = 25.0; // in m/s
+const EXHAUST_RATE: usize = 3; // particles per frame
+const SPARK_SPEED: f32 = 18.0;
+const SPARKS_PER_HIT: usize = 12;
+const SPARK_MIN_IMPULSE: f32 = 50.0; // softer contacts don't spark
+const DUST_MIN_SPEED: f32 = 8.0; // in m/s of the car on the ground
+const GOAL_EXPLOSION_PARTICLES: usize = 150;
+
+struct Particle {
+    position: Vector2<f32>,
+    velocity: Vector2<f32>,
+    life: f32, // in s left
+    max_life: f32,
+    size: f32, // in m
+    color: Color,
+    gravity_scale: f32,
+}
+
+pub struct ParticleSystem {
+    particles: Vec<Particle>,
+}
+
+fn random_unit() -> Vector2<f32> {
+    let angle = rand::gen_range(0.0, 2.0 * PI);
+    vector![angle.cos(), angle.sin()]
+}
+
+// direction rotated by a random angle within spread (in rad)
+fn jitter(direction: Vector2<f32>, spread: f32) -> Vector2<f32> {
+    let angle = rand::gen_range(-spread, spread);
+    let (sin, cos) = angle.sin_cos();
+    vector![
+        direction.x * cos - direction.y * sin,
+        direction.x * sin + direction.y * cos
+    ]
+}
+
+impl ParticleSystem {
+    pub fn new() -> ParticleSystem {
+        ParticleSystem {
+            particles: Vec::new(),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn spawn(
+        &mut self,
+        position: Vector2<f32>,
+        velocity: Vector2<f32>,
+        life: f32,
+        size: f32,
+        color: Color,
+        gravity_scale: f32,
+    ) {
+        if self.particles.len() >= MAX_PARTICLES {
+            return;
+        }
+        self.particles.push(Particle {
+            position,
+            velocity,
+            life,
+            max_life: life,
+            size,
+            color,
+            gravity_scale,
+        });
+    }
+
+    pub fn emit_exhaust(&mut self, nozzle: Vector2<f32>, direction: Vector2<f32>, color: Color) {
+        for _ in 0..EXHAUST_RATE {
+            let velocity = jitter(direction, 0.2) * EXHAUST_SPEED * rand::gen_range(0.6, 1.0);
+            let life = rand::gen_range(0.2, 0.4);
+            self.spawn(nozzle, velocity, life, 0.6, color, 0.0);
+        }
+    }
+
+    pub fn emit_sparks(&mut self, point: Vector2<f32>, impulse: f32) {
+        if impulse < SPARK_MIN_IMPULSE {
+            return;
+        }
+        for _ in 0..SPARKS_PER_HIT {
+            let velocity = random_unit() * SPARK_SPEED * rand::gen_range(0.3, 1.0);
+            let life = rand::gen_range(0.15, 0.35);
+            self.spawn(point, velocity, life, 0.25, YELLOW, 1.0);
+        }
+    }
+
+    pub fn emit_dust(&mut self, point: Vector2<f32>, car_velocity: Vector2<f32>) {
+        if car_velocity.norm() < DUST_MIN_SPEED {
+            return;
+        }
+        let velocity = vector![-car_velocity.x * 0.2, -rand::gen_range(1.0, 4.0)];
+        let life = rand::gen_range(0.3, 0.6);
+        let color = Color::new(0.75, 0.7, 0.6, 0.6);
+        self.spawn(point, velocity, life, 0.8, color, 0.2);
+    }
+
+    pub fn emit_goal_explosion(&mut self, position: Vector2<f32>, color: Color) {
+        for _ in 0..GOAL_EXPLOSION_PARTICLES {
+            let velocity = random_unit() * rand::gen_range(5.0, 40.0);
+            let life = rand::gen_range(0.6, 1.5);
+            let size = rand::gen_range(0.3, 1.0);
+            self.spawn(position, velocity, life, size, color, 0.5);
+        }
+    }
+
+    pub fn update(&mut self, dt: f32, gravity: Vector2<f32>) {
+        for particle in self.particles.iter_mut() {
+            particle.velocity += gravity * particle.gravity_scale * dt;
+            particle.position += particle.velocity * dt;
+            particle.life -= dt;
+        }
+        self.particles.retain(|particle| particle.life > 0.0);
+    }
+
+    // in the arena pixel space, so call it with the game camera active
+    pub fn draw(&self) {
+        for particle in self.particles.iter() {
+            let fade = particle.life / particle.max_life;
+            draw_circle(
+                pos_x_mtr_to_pxl(particle.position.x),
+                pos_y_mtr_to_pxl(particle.position.y),
+                size_mtr_to_pxl(particle.size * (0.5 + fade * 0.5)),
+                Color {
+                    a: particle.color.a * fade,
+                    ..particle.color
+                },
+            );
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use crate::nalgebra::Vector2;
+use crate::*;
+
+// BALL PREDICTION:
+// steps a throwaway world holding clones of the ball, the solids and the kinematic elements, so
+// the path bounces exactly like the real ball with the current restitution and gravity scale.
+// cars, the other balls and the map's trigger effects (boosters, portals, gravity zones) aren't
+// part of it, the path goes straight through them.
+const PREDICTION_DT: f32 = 1.0 / 60.0;
+const DOT_EVERY: usize = 4; // steps between two dots of the drawn trajectory
+
+pub struct BallPath {
+    pub dt: f32,
+    pub points: Vec<Vector2<f32>>, // ball position after each step, in m
+}
+
+impl BallPath {
+    // position after time seconds, None past the predicted horizon
+    #[allow(dead_code)] // for the AI, the game only draws the path
+    pub fn at(&self, time: f32) -> Option<Vector2<f32>> {
+        let step = (time / self.dt).max(0.0);
+        let index = step.floor() as usize;
+        match (self.points.get(index), self.points.get(index + 1)) {
+            (Some(a), Some(b)) => Some(a.lerp(b, step.fract())),
+            (Some(a), None) if step.fract() == 0.0 => Some(*a),
+            _ => None,
+        }
+    }
+
+    pub fn draw(&self) {
+        for (i, point) in self.points.iter().enumerate().step_by(DOT_EVERY) {
+            let fade = 1.0 - i as f32 / self.points.len() as f32;
+            draw_circle(
+                pos_x_mtr_to_pxl(point.x),
+                pos_y_mtr_to_pxl(point.y),
+                size_mtr_to_pxl(0.3),
+                Color::new(1.0, 1.0, 1.0, 0.8 * fade),
+            );
+        }
+    }
+}
+
+// the throwaway world is built once per match and only its ball gets synced before each run
+pub struct BallPredictor {
+    bodies: RigidBodySet,
+    colliders: ColliderSet,
+    joints: JointSet,
+    island_manager: IslandManager,
+    broad_phase: BroadPhase,
+    narrow_phase: NarrowPhase,
+    ccd_solver: CCDSolver,
+    physics_pipeline: PhysicsPipeline,
+    ball_handle: RigidBodyHandle,
+    ball_collider_handle: ColliderHandle,
+    kinematic_handles: Vec<RigidBodyHandle>, // same order as the match's kinematic elements
+}
+
+impl BallPredictor {
+    pub fn new(
+        ball: &FootBall,
+        solids: &[&Solid],
+        kinematics: &[KinematicElement],
+        body_set: &RigidBodySet,
+        coll_set: &ColliderSet,
+    ) -> BallPredictor {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        let mut clone_into_world =
+            |body_handle: RigidBodyHandle, collider_handle: ColliderHandle| {
+                let handle = bodies.insert(body_set[body_handle].clone());
+                let collider = colliders.insert_with_parent(
+                    coll_set[collider_handle].clone(),
+                    handle,
+                    &mut bodies,
+                );
+                (handle, collider)
+            };
+        for solid in solids.iter() {
+            clone_into_world(solid.body_handle, solid.collider_handle);
+        }
+        let kinematic_handles = kinematics
+            .iter()
+            .map(|element| clone_into_world(element.body_handle, element.collider_handle).0)
+            .collect();
+        let (ball_handle, ball_collider_handle) =
+            clone_into_world(ball.body_handle, ball.collider_handle);
+
+        BallPredictor {
+            bodies,
+            colliders,
+            joints: JointSet::new(),
+            island_manager: IslandManager::new(),
+            broad_phase: BroadPhase::new(),
+            narrow_phase: NarrowPhase::new(),
+            ccd_solver: CCDSolver::new(),
+            physics_pipeline: PhysicsPipeline::new(),
+            ball_handle,
+            ball_collider_handle,
+            kinematic_handles,
+        }
+    }
+
+    // path of the ball for the next horizon seconds, with the kinematic elements moving on from
+    // the match's arena time
+    #[allow(clippy::too_many_arguments)]
+    pub fn predict(
+        &mut self,
+        ball: &FootBall,
+        kinematics: &[KinematicElement],
+        arena_time: f32,
+        gravity: Vector2<f32>,
+        horizon: f32,
+        body_set: &RigidBodySet,
+        coll_set: &ColliderSet,
+    ) -> BallPath {
+        // the ball's shape and material can change with tuning, so they are synced too:
+        let source_body = &body_set[ball.body_handle];
+        let body = &mut self.bodies[self.ball_handle];
+        body.set_position(*source_body.position(), true);
+        body.set_linvel(*source_body.linvel(), true);
+        body.set_angvel(source_body.angvel(), true);
+        body.set_gravity_scale(source_body.gravity_scale(), true);
+        let source_collider = &coll_set[ball.collider_handle];
+        let collider = &mut self.colliders[self.ball_collider_handle];
+        collider.set_shape(source_collider.shared_shape().clone());
+        collider.set_restitution(source_collider.restitution());
+        collider.set_friction(source_collider.friction());
+        for (element, handle) in kinematics.iter().zip(self.kinematic_handles.iter()) {
+            let (translation, rotation) = element.motion.pose(arena_time);
+            self.bodies[*handle].set_position(Isometry::new(translation, rotation), true);
+        }
+
+        let integration_parameters = IntegrationParameters {
+            dt: PREDICTION_DT,
+            prediction_distance: 0.008,
+            ..Default::default()
+        };
+        let steps = (horizon / PREDICTION_DT).ceil() as usize;
+        let mut points = Vec::with_capacity(steps + 1);
+        points.push(*self.bodies[self.ball_handle].translation());
+        for step in 1..=steps {
+            let time = arena_time + step as f32 * PREDICTION_DT;
+            for (element, handle) in kinematics.iter().zip(self.kinematic_handles.iter()) {
+                let (translation, rotation) = element.motion.pose(time);
+                self.bodies[*handle]
+                    .set_next_kinematic_position(Isometry::new(translation, rotation));
+            }
+            self.physics_pipeline.step(
+                &gravity,
+                &integration_parameters,
+                &mut self.island_manager,
+                &mut self.broad_phase,
+                &mut self.narrow_phase,
+                &mut self.bodies,
+                &mut self.colliders,
+                &mut self.joints,
+                &mut self.ccd_solver,
+                &(),
+                &(),
+            );
+            points.push(*self.bodies[self.ball_handle].translation());
+        }
+
+        BallPath {
+            dt: PREDICTION_DT,
+            points,
+        }
+    }
+}
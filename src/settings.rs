@@ -0,0 +1,195 @@
+use crate::*;
+use std::path::PathBuf;
+
+// SETTINGS:
+// player preferences, kept as `key = value` lines in the user's config directory
+const SETTINGS_DIR: &str = "boink";
+const SETTINGS_FILE: &str = "settings.cfg";
+const LOCAL_PLAYERS: usize = 2;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Settings {
+    // controls:
+    pub controls: Vec<Controls>, // per local player
+    // audio:
+    pub master_volume: f32, // 0..1
+    pub effects_volume: f32,
+    // graphics:
+    pub fullscreen: bool,
+    pub camera_shake: bool,
+    pub ball_trajectory: bool,
+    // match rules:
+    pub game_mode: GameMode,
+    pub match_duration: f32, // in s
+    pub team_size: TeamSize,
+    pub car_hitbox: HitboxPreset,
+    pub drive_layout: DriveLayout,
+    pub ball_variant: BallVariant,
+    pub ball_count: usize, // 1..=MAX_BALLS
+    pub mutators: Mutators,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            controls: (0..LOCAL_PLAYERS).map(Controls::for_player).collect(),
+            master_volume: 0.8,
+            effects_volume: 1.0,
+            fullscreen: false,
+            camera_shake: true,
+            ball_trajectory: false,
+            game_mode: GameMode::Soccer,
+            match_duration: MATCH_DURATION,
+            team_size: TeamSize::OneVsOne,
+            car_hitbox: HitboxPreset::Octane,
+            drive_layout: DriveLayout::All,
+            ball_variant: BallVariant::Standard,
+            ball_count: 1,
+            mutators: Mutators::default(),
+        }
+    }
+}
+
+// platform config directory, falls back to the working directory
+pub fn settings_path() -> PathBuf {
+    let env_path = |name: &str| std::env::var_os(name).map(PathBuf::from);
+    let config_dir = match std::env::consts::OS {
+        "windows" => env_path("APPDATA"),
+        "macos" => env_path("HOME").map(|home| home.join("Library/Application Support")),
+        _ => env_path("XDG_CONFIG_HOME")
+            .or_else(|| env_path("HOME").map(|home| home.join(".config"))),
+    };
+    config_dir
+        .map(|dir| dir.join(SETTINGS_DIR))
+        .unwrap_or_default()
+        .join(SETTINGS_FILE)
+}
+
+impl Settings {
+    // unknown keys and unparsable values are skipped, missing ones keep their default
+    pub fn load() -> Settings {
+        let mut settings = Settings::default();
+        let content = match std::fs::read_to_string(settings_path()) {
+            Ok(content) => content,
+            Err(_) => return settings,
+        };
+        for (key, value) in parse_key_values(&content) {
+            match key {
+                "master_volume" => parse_into(value, &mut settings.master_volume),
+                "effects_volume" => parse_into(value, &mut settings.effects_volume),
+                "fullscreen" => parse_into(value, &mut settings.fullscreen),
+                "camera_shake" => parse_into(value, &mut settings.camera_shake),
+                "ball_trajectory" => parse_into(value, &mut settings.ball_trajectory),
+                "match_duration" => parse_into(value, &mut settings.match_duration),
+                "game_mode" => {
+                    if let Some(mode) = GameMode::ALL.iter().find(|mode| mode.name() == value) {
+                        settings.game_mode = *mode;
+                    }
+                }
+                "team_size" => {
+                    if let Some(team_size) = TeamSize::ALL.iter().find(|size| size.name() == value)
+                    {
+                        settings.team_size = *team_size;
+                    }
+                }
+                "ball_count" => {
+                    parse_into(value, &mut settings.ball_count);
+                    settings.ball_count = settings.ball_count.clamp(1, MAX_BALLS);
+                }
+                "ball_variant" => {
+                    if let Some(variant) = BallVariant::ALL
+                        .iter()
+                        .find(|variant| variant.name() == value)
+                    {
+                        settings.ball_variant = *variant;
+                    }
+                }
+                "car_hitbox" => {
+                    if let Some(hitbox) = HitboxPreset::ALL
+                        .iter()
+                        .find(|hitbox| hitbox.name() == value)
+                    {
+                        settings.car_hitbox = *hitbox;
+                    }
+                }
+                "drive_layout" => {
+                    if let Some(layout) = DriveLayout::ALL
+                        .iter()
+                        .find(|layout| layout.name() == value)
+                    {
+                        settings.drive_layout = *layout;
+                    }
+                }
+                _ => {
+                    // mutators as `mutator_ball_size = large`:
+                    for mutator in Mutator::ALL {
+                        if key == mutator_key(mutator) {
+                            settings.mutators.select_by_name(mutator, value);
+                        }
+                    }
+                    // controls as `p1_left = Left`:
+                    for (player, controls) in settings.controls.iter_mut().enumerate() {
+                        for (name, binding) in controls.fields_mut() {
+                            if key == format!("p{}_{}", player + 1, name) {
+                                if let Some(parsed) = parse_key(value) {
+                                    *binding = parsed;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        settings
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let path = settings_path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let mut content = String::from("# boink settings\n");
+        let mut settings = self.clone();
+        for (player, controls) in settings.controls.iter_mut().enumerate() {
+            for (name, binding) in controls.fields_mut() {
+                content.push_str(&format!(
+                    "p{}_{} = {}\n",
+                    player + 1,
+                    name,
+                    key_name(*binding)
+                ));
+            }
+        }
+        content.push_str(&format!("master_volume = {}\n", self.master_volume));
+        content.push_str(&format!("effects_volume = {}\n", self.effects_volume));
+        content.push_str(&format!("fullscreen = {}\n", self.fullscreen));
+        content.push_str(&format!("camera_shake = {}\n", self.camera_shake));
+        content.push_str(&format!("ball_trajectory = {}\n", self.ball_trajectory));
+        content.push_str(&format!("match_duration = {}\n", self.match_duration));
+        content.push_str(&format!("game_mode = {}\n", self.game_mode.name()));
+        content.push_str(&format!("team_size = {}\n", self.team_size.name()));
+        content.push_str(&format!("car_hitbox = {}\n", self.car_hitbox.name()));
+        content.push_str(&format!("drive_layout = {}\n", self.drive_layout.name()));
+        content.push_str(&format!("ball_variant = {}\n", self.ball_variant.name()));
+        content.push_str(&format!("ball_count = {}\n", self.ball_count));
+        for mutator in Mutator::ALL {
+            content.push_str(&format!(
+                "{} = {}\n",
+                mutator_key(mutator),
+                self.mutators.option_name(mutator)
+            ));
+        }
+        std::fs::write(path, content)
+    }
+}
+
+fn mutator_key(mutator: Mutator) -> String {
+    format!("mutator_{}", mutator.name().replace(' ', "_"))
+}
+
+fn parse_into<T: std::str::FromStr>(value: &str, field: &mut T) {
+    if let Ok(value) = value.parse() {
+        *field = value;
+    }
+}
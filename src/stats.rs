@@ -0,0 +1,212 @@
+use crate::car::{Car, CarStates};
+use crate::*;
+use std::collections::BTreeMap;
+
+// MATCH STATS:
+// per player numbers over a match, fed by the game events and by sampling the cars every frame.
+pub const STATS_PATH: &str = "match_stats.json";
+
+#[derive(Clone, Default, Debug)]
+pub struct PlayerStats {
+    pub goals: u32,
+    pub own_goals: u32,
+    pub assists: u32,
+    pub saves: u32,
+    pub shots: u32,
+    pub touches: u32,
+    pub time_ground: f32, // in s
+    pub time_air: f32,
+    pub time_wall: f32,
+    pub boost_used: f32,
+    pub max_speed: f32, // in m/s
+    pub distance: f32,  // in m, driven on ground and walls
+}
+
+pub struct MatchStats {
+    pub players: BTreeMap<CarId, PlayerStats>,
+    pub status: String, // how the last export went
+}
+
+impl MatchStats {
+    pub fn new() -> MatchStats {
+        MatchStats {
+            players: BTreeMap::new(),
+            status: String::new(),
+        }
+    }
+
+    fn player(&mut self, car: CarId) -> &mut PlayerStats {
+        self.players.entry(car).or_default()
+    }
+
+    pub fn sample_car(&mut self, car: &Car, body_set: &RigidBodySet, dt: f32) {
+        let speed = car.get_speed(body_set);
+        let stats = self.player(car.id);
+        match car.get_car_state() {
+            CarStates::Ground => stats.time_ground += dt,
+            CarStates::Air => stats.time_air += dt,
+            CarStates::Wall => stats.time_wall += dt,
+        }
+        if car.get_car_state() != CarStates::Air {
+            stats.distance += speed * dt;
+        }
+        stats.max_speed = stats.max_speed.max(speed);
+    }
+
+    pub fn add_boost_used(&mut self, car: CarId, amount: f32) {
+        self.player(car).boost_used += amount;
+    }
+
+    pub fn to_json(&self, score: &Score) -> String {
+        let players: Vec<String> = self
+            .players
+            .iter()
+            .map(|(car, stats)| {
+                let fields = [
+                    ("car", car.to_string()),
+                    ("goals", stats.goals.to_string()),
+                    ("own_goals", stats.own_goals.to_string()),
+                    ("assists", stats.assists.to_string()),
+                    ("saves", stats.saves.to_string()),
+                    ("shots", stats.shots.to_string()),
+                    ("touches", stats.touches.to_string()),
+                    ("time_ground", format!("{:.2}", stats.time_ground)),
+                    ("time_air", format!("{:.2}", stats.time_air)),
+                    ("time_wall", format!("{:.2}", stats.time_wall)),
+                    ("boost_used", format!("{:.1}", stats.boost_used)),
+                    ("max_speed", format!("{:.2}", stats.max_speed)),
+                    ("distance", format!("{:.1}", stats.distance)),
+                ];
+                format!("    {}", json_object(&fields))
+            })
+            .collect();
+        let score_fields = [
+            ("left", score.left.to_string()),
+            ("right", score.right.to_string()),
+        ];
+        format!(
+            "{{\n  {}: {},\n  {}: [\n{}\n  ]\n}}\n",
+            json_string("score"),
+            json_object(&score_fields),
+            json_string("players"),
+            players.join(",\n")
+        )
+    }
+
+    // the outcome ends up on the full time scoreboard
+    pub fn export(&mut self, score: &Score, path: &str) {
+        self.status = match std::fs::write(path, self.to_json(score)) {
+            Ok(()) => format!("stats saved to {}", path),
+            Err(err) => format!("could not export match stats: {}", err),
+        };
+    }
+}
+
+// values are already json, only the keys get quoted
+fn json_object(fields: &[(&str, String)]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|(key, value)| format!("{}: {}", json_string(key), value))
+        .collect();
+    format!("{{{}}}", fields.join(", "))
+}
+
+fn json_string(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+impl GameEventSubscriber for MatchStats {
+    fn on_event(&mut self, event: &GameEvent) {
+        match *event {
+            GameEvent::BallTouched { car, .. } => self.player(car).touches += 1,
+            GameEvent::Shot { car, .. } => self.player(car).shots += 1,
+            GameEvent::Save { car, .. } => self.player(car).saves += 1,
+            GameEvent::GoalAttributed {
+                scorer,
+                assist,
+                own_goal,
+                ..
+            } => {
+                match (scorer, own_goal) {
+                    (Some(scorer), false) => self.player(scorer).goals += 1,
+                    (Some(scorer), true) => self.player(scorer).own_goals += 1,
+                    (None, _) => {}
+                }
+                if let Some(assist) = assist {
+                    self.player(assist).assists += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// SCOREBOARD:
+pub fn draw_scoreboard(stats: &MatchStats, score: &Score, title: &str) {
+    let view = viewport();
+    let font_size = view.h / 28.0;
+    let line = font_size * 1.2;
+    let (x, mut y) = (view.x + view.w * 0.1, view.y + view.h * 0.15);
+
+    draw_rectangle(
+        view.x + view.w * 0.05,
+        view.y + view.h * 0.05,
+        view.w * 0.9,
+        view.h * 0.9,
+        Color::new(0.0, 0.0, 0.0, 0.75),
+    );
+    draw_text(
+        &format!("{}   {} : {}", title, score.left, score.right),
+        x,
+        y,
+        font_size * 1.5,
+        WHITE,
+    );
+    y += line * 2.0;
+
+    let columns = [
+        "player", "goals", "assists", "saves", "shots", "touches", "ground", "air", "wall",
+        "boost", "max km/h", "dist m",
+    ];
+    let column_w = view.w * 0.8 / columns.len() as f32;
+    for (i, column) in columns.iter().enumerate() {
+        draw_text(column, x + i as f32 * column_w, y, font_size, LIGHTGRAY);
+    }
+    y += line;
+
+    for (car, player) in stats.players.iter() {
+        let values = [
+            format!("{}", car + 1),
+            player.goals.to_string(),
+            player.assists.to_string(),
+            player.saves.to_string(),
+            player.shots.to_string(),
+            player.touches.to_string(),
+            format!("{:.0}s", player.time_ground),
+            format!("{:.0}s", player.time_air),
+            format!("{:.0}s", player.time_wall),
+            format!("{:.0}", player.boost_used),
+            format!("{:.0}", player.max_speed * 3.6),
+            format!("{:.0}", player.distance),
+        ];
+        for (i, value) in values.iter().enumerate() {
+            draw_text(value, x + i as f32 * column_w, y, font_size, WHITE);
+        }
+        y += line;
+    }
+    y += line;
+    draw_text(&stats.status, x, y, font_size, LIGHTGRAY);
+}
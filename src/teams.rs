@@ -0,0 +1,120 @@
+use crate::car::{Car, CarSpec};
+use crate::nalgebra::Vector2;
+use crate::*;
+
+// TEAMS:
+// every car plays for a side. the left side defends the left goal, the right side the right one.
+// kickoff spots are given as distance from the own back wall in fractions of the arena width,
+// front car first.
+const KICKOFF_1V1: [f32; 1] = [0.25];
+const KICKOFF_2V2: [f32; 2] = [0.3, 0.15];
+const KICKOFF_3V3: [f32; 3] = [0.37, 0.26, 0.15];
+const KICKOFF_HEIGHT: f32 = 4.0; // in m above the arena bottom
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TeamSize {
+    OneVsOne,
+    TwoVsTwo,
+    ThreeVsThree,
+}
+
+impl TeamSize {
+    pub const ALL: [TeamSize; 3] = [
+        TeamSize::OneVsOne,
+        TeamSize::TwoVsTwo,
+        TeamSize::ThreeVsThree,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TeamSize::OneVsOne => "1v1",
+            TeamSize::TwoVsTwo => "2v2",
+            TeamSize::ThreeVsThree => "3v3",
+        }
+    }
+
+    fn kickoff_fractions(self) -> &'static [f32] {
+        match self {
+            TeamSize::OneVsOne => &KICKOFF_1V1,
+            TeamSize::TwoVsTwo => &KICKOFF_2V2,
+            TeamSize::ThreeVsThree => &KICKOFF_3V3,
+        }
+    }
+
+    // kickoff formation of one side, mirrored for the right half
+    pub fn kickoff_spots(self, side: Side) -> Vec<Vector2<f32>> {
+        self.kickoff_fractions()
+            .iter()
+            .map(|fraction| {
+                let x = match side {
+                    Side::Left => ARENA_WIDTH * fraction,
+                    Side::Right => ARENA_WIDTH * (1.0 - fraction),
+                };
+                vector![x, ARENA_HEIGHT - KICKOFF_HEIGHT]
+            })
+            .collect()
+    }
+}
+
+pub fn team_color(side: Side) -> Color {
+    match side {
+        Side::Left => Color::new(0.2, 0.45, 1.0, 1.0),
+        Side::Right => Color::new(1.0, 0.55, 0.1, 1.0),
+    }
+}
+
+pub fn team_name(side: Side) -> &'static str {
+    match side {
+        Side::Left => "blue",
+        Side::Right => "orange",
+    }
+}
+
+// deepest kickoff spot, in front of the own goal
+pub fn respawn_point(side: Side) -> Vector2<f32> {
+    *TeamSize::ThreeVsThree.kickoff_spots(side).last().unwrap()
+}
+
+// both teams with team_size cars each, the car id is its index
+pub fn spawn_teams(
+    team_size: TeamSize,
+    spec: &CarSpec,
+    body_set: &mut RigidBodySet,
+    coll_set: &mut ColliderSet,
+    joint_set: &mut JointSet,
+) -> Vec<Car> {
+    let mut cars = Vec::new();
+    for side in [Side::Left, Side::Right] {
+        for spot in team_size.kickoff_spots(side) {
+            cars.push(Car::new(
+                cars.len(),
+                side,
+                spec.clone(),
+                spot,
+                body_set,
+                coll_set,
+                joint_set,
+            ));
+        }
+    }
+    cars
+}
+
+// puts every car back on its kickoff spot, demolished ones included
+pub fn kickoff(
+    cars: &mut [Car],
+    team_size: TeamSize,
+    body_set: &mut RigidBodySet,
+    coll_set: &mut ColliderSet,
+) {
+    for side in [Side::Left, Side::Right] {
+        let spots = team_size.kickoff_spots(side);
+        for (car, spot) in cars.iter_mut().filter(|car| car.side == side).zip(spots) {
+            if car.is_demolished() {
+                car.respawn(spot, body_set, coll_set);
+            } else {
+                car.place(spot, body_set, coll_set);
+            }
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use crate::car::Car;
+use crate::nalgebra::Vector2;
+use crate::*;
+
+// BALL TOUCHES:
+// who touched which ball when, read from the narrow phase contact pairs between the ball and
+// the car colliders. used to attribute goals, assists, shots, saves and own goals.
+const ASSIST_WINDOW: f32 = 5.0; // in s of match time before the scoring touch
+const TOUCH_HISTORY: usize = 8; // per ball
+const PREDICTION_HORIZON: f32 = 3.0; // in s, how far ahead a shot is checked
+
+#[derive(Clone, Copy, Debug)]
+pub struct BallTouch {
+    pub car: CarId,
+    pub time: f32, // match time, stands still while paused
+    #[allow(dead_code)] // for the AI, nothing in the game reads it yet
+    pub relative_velocity: Vector2<f32>, // ball relative to the car, at the moment of the touch
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct GoalAttribution {
+    pub scorer: Option<CarId>,
+    pub assist: Option<CarId>,
+    pub own_goal: bool,
+}
+
+pub struct TouchTracker {
+    touches: HashMap<usize, Vec<BallTouch>>,     // latest last
+    touching: Vec<(usize, CarId)>,               // contacts that are still going on
+    last_velocity: HashMap<usize, Vector2<f32>>, // ball velocity before this step
+}
+
+impl TouchTracker {
+    pub fn new() -> TouchTracker {
+        TouchTracker {
+            touches: HashMap::new(),
+            touching: Vec::new(),
+            last_velocity: HashMap::new(),
+        }
+    }
+
+    #[allow(dead_code)] // for the AI, see relative_velocity
+    pub fn last_touch(&self, ball: usize) -> Option<&BallTouch> {
+        self.touches.get(&ball).and_then(|touches| touches.last())
+    }
+
+    // records new touches and returns the shots, saves and goal attributions of this step.
+    // call after the physics step with the events collected from it and the match time.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update(
+        &mut self,
+        balls: &[&FootBall],
+        cars: &[&Car],
+        goals: &[Goal],
+        team_of: &dyn Fn(CarId) -> Side,
+        gravity: Vector2<f32>,
+        events: &[GameEvent],
+        time: f32,
+        body_set: &RigidBodySet,
+        narrow_phase: &NarrowPhase,
+    ) -> Vec<GameEvent> {
+        let mut new_events = Vec::new();
+        let mut still_touching = Vec::new();
+
+        for (ball_index, ball) in balls.iter().enumerate() {
+            let ball_body = &body_set[ball.body_handle];
+            let ball_gravity = gravity * ball_body.gravity_scale();
+            let velocity_before = self
+                .last_velocity
+                .get(&ball_index)
+                .copied()
+                .unwrap_or(*ball_body.linvel());
+
+            for car in cars.iter() {
+                let in_contact = car.collider_handles().iter().any(|handle| {
+                    narrow_phase
+                        .contact_pair(ball.collider_handle, *handle)
+                        .is_some_and(|pair| pair.has_any_active_contact)
+                });
+                if !in_contact {
+                    continue;
+                }
+                still_touching.push((ball_index, car.id));
+                if self.touching.contains(&(ball_index, car.id)) {
+                    continue;
+                }
+
+                let car_velocity = body_set[car.body_handle()]
+                    .velocity_at_point(&Point::from(*ball_body.translation()));
+                let touch = BallTouch {
+                    car: car.id,
+                    time,
+                    relative_velocity: ball_body.linvel() - car_velocity,
+                };
+                let history = self.touches.entry(ball_index).or_default();
+                history.push(touch);
+                if history.len() > TOUCH_HISTORY {
+                    history.remove(0);
+                }
+
+                // shots head for the opponent goal after the touch, saves stop one heading for their own:
+                let side = team_of(car.id);
+                let position = *ball_body.translation();
+                for goal in goals.iter() {
+                    if goal.side != side
+                        && heading_into_goal(position, *ball_body.linvel(), ball_gravity, goal)
+                    {
+                        new_events.push(GameEvent::Shot {
+                            car: car.id,
+                            ball: ball_index,
+                        });
+                    }
+                    if goal.side == side
+                        && heading_into_goal(position, velocity_before, ball_gravity, goal)
+                        && !heading_into_goal(position, *ball_body.linvel(), ball_gravity, goal)
+                    {
+                        new_events.push(GameEvent::Save {
+                            car: car.id,
+                            ball: ball_index,
+                        });
+                    }
+                }
+            }
+            self.last_velocity.insert(ball_index, *ball_body.linvel());
+        }
+        self.touching = still_touching;
+
+        for event in events.iter() {
+            if let GameEvent::GoalScored { side, ball } = *event {
+                let attribution = self.attribute_goal(ball, side, team_of);
+                new_events.push(GameEvent::GoalAttributed {
+                    side,
+                    ball,
+                    scorer: attribution.scorer,
+                    assist: attribution.assist,
+                    own_goal: attribution.own_goal,
+                });
+                // the ball goes back to kickoff, earlier touches don't count for the next goal:
+                self.touches.remove(&ball);
+            }
+        }
+
+        new_events
+    }
+
+    pub fn attribute_goal(
+        &self,
+        ball: usize,
+        scoring_side: Side,
+        team_of: &dyn Fn(CarId) -> Side,
+    ) -> GoalAttribution {
+        let touches = match self.touches.get(&ball) {
+            Some(touches) if !touches.is_empty() => touches,
+            _ => {
+                return GoalAttribution {
+                    scorer: None,
+                    assist: None,
+                    own_goal: false,
+                }
+            }
+        };
+        let last = touches[touches.len() - 1];
+
+        // an own goal still counts for the other side, the scorer is the unlucky defender:
+        if team_of(last.car) != scoring_side {
+            return GoalAttribution {
+                scorer: Some(last.car),
+                assist: None,
+                own_goal: true,
+            };
+        }
+
+        // latest touch before the scorer's by a team mate, as long as no opponent touched in between:
+        let assist = touches[..touches.len() - 1]
+            .iter()
+            .rev()
+            .take_while(|touch| team_of(touch.car) == scoring_side)
+            .find(|touch| touch.car != last.car && last.time - touch.time <= ASSIST_WINDOW)
+            .map(|touch| touch.car);
+
+        GoalAttribution {
+            scorer: Some(last.car),
+            assist,
+            own_goal: false,
+        }
+    }
+}
+
+// ballistic check whether the ball goes through the goal mouth, sideways through the upright
+// mouth of a goal or dropping into the flat one of a hoop
+fn heading_into_goal(
+    position: Vector2<f32>,
+    velocity: Vector2<f32>,
+    gravity: Vector2<f32>,
+    goal: &Goal,
+) -> bool {
+    let (center, size) = goal.mouth;
+    if size.y >= size.x {
+        if velocity.x.abs() < f32::EPSILON {
+            return false;
+        }
+        let t = (center.x - position.x) / velocity.x;
+        if !(0.0..=PREDICTION_HORIZON).contains(&t) {
+            return false;
+        }
+        let y = position.y + velocity.y * t + 0.5 * gravity.y * t * t;
+        (y - center.y).abs() <= size.y / 2.0
+    } else {
+        match dropping_through(position.y, velocity.y, gravity.y, center.y) {
+            Some(t) if t <= PREDICTION_HORIZON => {
+                let x = position.x + velocity.x * t;
+                (x - center.x).abs() <= size.x / 2.0
+            }
+            _ => false,
+        }
+    }
+}
+
+// first time from now the ball passes the height `line` on its way down, y grows downwards
+fn dropping_through(y: f32, velocity: f32, gravity: f32, line: f32) -> Option<f32> {
+    let distance = line - y;
+    if gravity.abs() < f32::EPSILON {
+        return if velocity > 0.0 && distance >= 0.0 {
+            Some(distance / velocity)
+        } else {
+            None
+        };
+    }
+    let discriminant = velocity * velocity + 2.0 * gravity * distance;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let root = discriminant.sqrt();
+    [(-velocity - root) / gravity, (-velocity + root) / gravity]
+        .iter()
+        .copied()
+        .filter(|t| *t >= 0.0 && velocity + gravity * t > 0.0)
+        .reduce(f32::min)
+}
@@ -0,0 +1,341 @@
+use crate::car::Car;
+use crate::nalgebra::Vector2;
+use crate::*;
+
+// TRIGGERS AND BUMPERS:
+// the map's static effect volumes. triggers are sensors reporting what enters and leaves them,
+// bumpers are solid blocks kicking back whatever touches them. effects act on the whole car, body
+// and wheels alike, so the joints don't get torn apart.
+const BUMPER_COLOR: Color = RED;
+const BOOSTER_COLOR: Color = Color::new(1.0, 0.63, 0.0, 0.35);
+const LAUNCH_PAD_COLOR: Color = Color::new(0.0, 0.89, 0.19, 0.35);
+const PORTAL_COLOR: Color = Color::new(0.44, 0.12, 0.75, 0.5);
+const GRAVITY_ZONE_COLOR: Color = Color::new(0.4, 0.75, 1.0, 0.2);
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TriggerEffect {
+    BoostBall { velocity: Vector2<f32> },
+    LaunchCar { velocity: Vector2<f32> },
+    Portal { exit: Vector2<f32> },
+    Gravity { gravity: Vector2<f32> },
+}
+
+pub struct Trigger {
+    pub effect: TriggerEffect,
+    pub center: Vector2<f32>,
+    pub size: Vector2<f32>,
+    pub sensor_handle: ColliderHandle,
+}
+
+impl Trigger {
+    pub fn new(
+        effect: TriggerEffect,
+        center: Vector2<f32>,
+        size: Vector2<f32>,
+        body_set: &mut RigidBodySet,
+        coll_set: &mut ColliderSet,
+    ) -> Trigger {
+        let body = RigidBodyBuilder::new_static().translation(center).build();
+        let body_handle = body_set.insert(body);
+        let collider = ColliderBuilder::cuboid(size.x / 2.0, size.y / 2.0)
+            .sensor(true)
+            .collision_groups(groups(&[CollisionLayer::Trigger]))
+            .active_events(ActiveEvents::INTERSECTION_EVENTS)
+            .build();
+        let sensor_handle = coll_set.insert_with_parent(collider, body_handle, body_set);
+
+        Trigger {
+            effect,
+            center,
+            size,
+            sensor_handle,
+        }
+    }
+
+    pub fn draw(&self) {
+        let color = match self.effect {
+            TriggerEffect::BoostBall { .. } => BOOSTER_COLOR,
+            TriggerEffect::LaunchCar { .. } => LAUNCH_PAD_COLOR,
+            TriggerEffect::Portal { .. } => PORTAL_COLOR,
+            TriggerEffect::Gravity { .. } => GRAVITY_ZONE_COLOR,
+        };
+        draw_rectangle(
+            pos_x_mtr_to_pxl(self.center.x - self.size.x / 2.0),
+            pos_y_mtr_to_pxl(self.center.y - self.size.y / 2.0),
+            size_mtr_to_pxl(self.size.x),
+            size_mtr_to_pxl(self.size.y),
+            color,
+        );
+    }
+}
+
+pub struct Bumper {
+    pub solid: Solid,
+    pub strength: f32, // in m/s, speed away from the bumper after a hit
+}
+
+pub struct ArenaTriggers {
+    pub triggers: Vec<Trigger>,
+    pub bumpers: Vec<Bumper>,
+    inside: Vec<(usize, Target)>,   // who is in which gravity zone
+    arrivals: Vec<(usize, Target)>, // portal ends entered by teleporting, ignored until left
+}
+
+impl ArenaTriggers {
+    pub fn new(
+        map: &MapData,
+        body_set: &mut RigidBodySet,
+        coll_set: &mut ColliderSet,
+    ) -> ArenaTriggers {
+        let mut triggers = Vec::new();
+        let mut bumpers = Vec::new();
+        for entity in map.entities.iter() {
+            match *entity {
+                MapEntity::Bumper {
+                    size,
+                    center,
+                    strength,
+                } => bumpers.push(Bumper {
+                    solid: Solid::new_with_contact_event(
+                        center - size / 2.0,
+                        size,
+                        body_set,
+                        coll_set,
+                    ),
+                    strength,
+                }),
+                MapEntity::BallBooster {
+                    size,
+                    center,
+                    velocity,
+                } => triggers.push(Trigger::new(
+                    TriggerEffect::BoostBall { velocity },
+                    center,
+                    size,
+                    body_set,
+                    coll_set,
+                )),
+                MapEntity::LaunchPad {
+                    size,
+                    center,
+                    velocity,
+                } => triggers.push(Trigger::new(
+                    TriggerEffect::LaunchCar { velocity },
+                    center,
+                    size,
+                    body_set,
+                    coll_set,
+                )),
+                MapEntity::Portal { size, ends } => {
+                    for (entry, exit) in [(ends[0], ends[1]), (ends[1], ends[0])] {
+                        triggers.push(Trigger::new(
+                            TriggerEffect::Portal { exit },
+                            entry,
+                            size,
+                            body_set,
+                            coll_set,
+                        ));
+                    }
+                }
+                MapEntity::GravityZone {
+                    size,
+                    center,
+                    gravity,
+                } => triggers.push(Trigger::new(
+                    TriggerEffect::Gravity { gravity },
+                    center,
+                    size,
+                    body_set,
+                    coll_set,
+                )),
+                _ => {}
+            }
+        }
+
+        ArenaTriggers {
+            triggers,
+            bumpers,
+            inside: Vec::new(),
+            arrivals: Vec::new(),
+        }
+    }
+
+    pub fn register_colliders(&self, owners: &mut ColliderOwners) {
+        for (i, trigger) in self.triggers.iter().enumerate() {
+            owners.insert(trigger.sensor_handle, ColliderOwner::Trigger(i));
+        }
+        for (i, bumper) in self.bumpers.iter().enumerate() {
+            owners.insert(bumper.solid.collider_handle, ColliderOwner::Bumper(i));
+        }
+    }
+
+    // zone gravity replaces the world's, as a force on top of it. call before every step.
+    pub fn apply_gravity(
+        &mut self,
+        world_gravity: Vector<f32>,
+        balls: &[&FootBall],
+        cars: &[Car],
+        body_set: &mut RigidBodySet,
+    ) {
+        // demolished cars leave without an event:
+        self.inside
+            .retain(|(_, target)| !target_bodies(*target, balls, cars).is_empty());
+        for (trigger, target) in self.inside.iter() {
+            let gravity = match self.triggers[*trigger].effect {
+                TriggerEffect::Gravity { gravity } => gravity,
+                _ => continue,
+            };
+            for handle in target_bodies(*target, balls, cars) {
+                let body = &mut body_set[handle];
+                let force = (gravity - world_gravity) * body.mass() * body.gravity_scale();
+                body.apply_force(force, true);
+            }
+        }
+    }
+
+    // call after the step with the events collected from it
+    pub fn handle_events(
+        &mut self,
+        events: &[GameEvent],
+        balls: &[&FootBall],
+        cars: &[Car],
+        body_set: &mut RigidBodySet,
+    ) {
+        // wheels and body hitting a bumper in the same step get kicked once:
+        let mut kicked = Vec::new();
+        for event in events.iter() {
+            match *event {
+                GameEvent::BumperHit {
+                    bumper,
+                    target,
+                    point,
+                } => {
+                    if kicked.contains(&target) {
+                        continue;
+                    }
+                    kicked.push(target);
+                    let bumper = &self.bumpers[bumper];
+                    let from = point.unwrap_or(*body_set[bumper.solid.body_handle].translation());
+                    kick(target, from, bumper.strength, balls, cars, body_set);
+                }
+                GameEvent::TriggerEntered { trigger, target } => {
+                    if self.arrivals.contains(&(trigger, target)) {
+                        continue;
+                    }
+                    match (self.triggers[trigger].effect, target) {
+                        (TriggerEffect::BoostBall { velocity }, Target::Ball(_))
+                        | (TriggerEffect::LaunchCar { velocity }, Target::Car(_)) => {
+                            for handle in target_bodies(target, balls, cars) {
+                                let body = &mut body_set[handle];
+                                let linvel = *body.linvel();
+                                body.set_linvel(linvel + velocity, true);
+                            }
+                        }
+                        (TriggerEffect::Portal { exit }, _) => {
+                            teleport(target, exit, balls, cars, body_set);
+                            if let Some(exit_trigger) = self.portal_end_at(exit) {
+                                self.arrivals.push((exit_trigger, target));
+                            }
+                        }
+                        (TriggerEffect::Gravity { .. }, _) => self.inside.push((trigger, target)),
+                        _ => {}
+                    }
+                }
+                GameEvent::TriggerLeft { trigger, target } => {
+                    self.inside.retain(|entry| *entry != (trigger, target));
+                    self.arrivals.retain(|entry| *entry != (trigger, target));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn portal_end_at(&self, center: Vector2<f32>) -> Option<usize> {
+        self.triggers.iter().position(|trigger| {
+            matches!(trigger.effect, TriggerEffect::Portal { .. }) && trigger.center == center
+        })
+    }
+
+    pub fn draw(&self, body_set: &RigidBodySet) {
+        for trigger in self.triggers.iter() {
+            trigger.draw();
+        }
+        for bumper in self.bumpers.iter() {
+            let translation = body_set[bumper.solid.body_handle].translation();
+            let size = bumper.solid.size;
+            draw_rectangle(
+                pos_x_mtr_to_pxl(translation.x - size.x / 2.0),
+                pos_y_mtr_to_pxl(translation.y - size.y / 2.0),
+                size_mtr_to_pxl(size.x),
+                size_mtr_to_pxl(size.y),
+                BUMPER_COLOR,
+            );
+        }
+    }
+}
+
+// the main body first, none for cars out of play
+fn target_bodies(target: Target, balls: &[&FootBall], cars: &[Car]) -> Vec<RigidBodyHandle> {
+    match target {
+        Target::Ball(ball) => vec![balls[ball].body_handle],
+        Target::Car(car) if cars[car].is_demolished() => Vec::new(),
+        Target::Car(car) => {
+            let main = cars[car].body_handle();
+            std::iter::once(main)
+                .chain(
+                    cars[car]
+                        .body_handles()
+                        .into_iter()
+                        .filter(|handle| *handle != main),
+                )
+                .collect()
+        }
+    }
+}
+
+// at least strength m/s away from the hit point, the same change for every part of a car
+fn kick(
+    target: Target,
+    from: Vector2<f32>,
+    strength: f32,
+    balls: &[&FootBall],
+    cars: &[Car],
+    body_set: &mut RigidBodySet,
+) {
+    let handles = target_bodies(target, balls, cars);
+    let main = match handles.first() {
+        Some(main) => &body_set[*main],
+        None => return,
+    };
+    let away = main.translation() - from;
+    if away.norm() <= f32::EPSILON {
+        return;
+    }
+    let direction = away.normalize();
+    let missing = (strength - main.linvel().dot(&direction)).max(0.0);
+    for handle in handles {
+        let body = &mut body_set[handle];
+        let linvel = *body.linvel();
+        body.set_linvel(linvel + direction * missing, true);
+    }
+}
+
+// moves the target's main body onto the spot, keeping its velocity
+fn teleport(
+    target: Target,
+    spot: Vector2<f32>,
+    balls: &[&FootBall],
+    cars: &[Car],
+    body_set: &mut RigidBodySet,
+) {
+    let handles = target_bodies(target, balls, cars);
+    let offset = match handles.first() {
+        Some(main) => spot - body_set[*main].translation(),
+        None => return,
+    };
+    for handle in handles {
+        let body = &mut body_set[handle];
+        let translation = *body.translation();
+        body.set_translation(translation + offset, true);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::*;
+use macroquad::hash;
+use macroquad::ui::root_ui;
+use std::ops::Range;
+
+// TUNING:
+// live physics parameters, editable in game with F2 and exported as `key = value` lines.
+pub const TUNING_PATH: &str = "tuning.cfg";
+
+#[derive(Clone, PartialEq)]
+pub struct Tuning {
+    pub gravity: f32,
+    pub ball_grav_scale: f32,
+    pub ball_restitution: f32,
+    pub wheel_friction: f32,
+    pub car_linear_damping: f32,
+    pub car_angular_damping: f32,
+    pub drive_torque: f32,
+    pub jump_impulse: f32,
+    pub boost_force: f32,
+    pub air_max_angvel: f32,     // in rad/s
+    pub air_responsiveness: f32, // in 1/s, how fast the car spins up to the target
+}
+
+impl Default for Tuning {
+    fn default() -> Tuning {
+        Tuning {
+            gravity: GRAVITY,
+            ball_grav_scale: BALL_GRAV_SCALE,
+            ball_restitution: BALL_RESTITUTION,
+            wheel_friction: WHEEL_FRICTION,
+            car_linear_damping: CAR_LINEAR_DAMPING,
+            car_angular_damping: CAR_ANGULAR_DAMPING,
+            drive_torque: DRIVE_TORQUE,
+            jump_impulse: JUMP_IMPULSE,
+            boost_force: BOOST_FORCE,
+            air_max_angvel: AIR_MAX_ANGVEL,
+            air_responsiveness: AIR_RESPONSIVENESS,
+        }
+    }
+}
+
+impl Tuning {
+    // name, value and slider range of every parameter, shared by the console and the config file
+    fn fields_mut(&mut self) -> [(&'static str, &mut f32, Range<f32>); 11] {
+        [
+            ("gravity", &mut self.gravity, 0.0..100.0),
+            ("ball_grav_scale", &mut self.ball_grav_scale, 0.0..2.0),
+            ("ball_restitution", &mut self.ball_restitution, 0.0..1.5),
+            ("wheel_friction", &mut self.wheel_friction, 0.0..20.0),
+            ("car_linear_damping", &mut self.car_linear_damping, 0.0..5.0),
+            (
+                "car_angular_damping",
+                &mut self.car_angular_damping,
+                0.0..20.0,
+            ),
+            ("drive_torque", &mut self.drive_torque, 0.0..20000.0),
+            ("jump_impulse", &mut self.jump_impulse, 0.0..20000.0),
+            ("boost_force", &mut self.boost_force, 0.0..1000.0),
+            ("air_max_angvel", &mut self.air_max_angvel, 0.0..15.0),
+            (
+                "air_responsiveness",
+                &mut self.air_responsiveness,
+                0.0..30.0,
+            ),
+        ]
+    }
+
+    pub fn gravity_vector(&self) -> Vector<f32> {
+        vector![0.0, self.gravity]
+    }
+
+    // unknown keys and unparsable values are skipped, missing ones keep their default
+    pub fn load(path: &str) -> Tuning {
+        let mut tuning = Tuning::default();
+        if let Ok(content) = std::fs::read_to_string(path) {
+            for (key, value) in parse_key_values(&content) {
+                if let Ok(value) = value.parse::<f32>() {
+                    for (name, field, _) in tuning.fields_mut() {
+                        if name == key {
+                            *field = value;
+                        }
+                    }
+                }
+            }
+        }
+        tuning
+    }
+
+    pub fn export(&self, path: &str) -> std::io::Result<()> {
+        let mut tuning = self.clone();
+        let mut content = String::from("# boink physics tuning\n");
+        for (name, field, _) in tuning.fields_mut() {
+            content.push_str(&format!("{} = {}\n", name, field));
+        }
+        std::fs::write(path, content)
+    }
+}
+
+// `key = value` lines, `#` starts a comment
+pub fn parse_key_values(content: &str) -> Vec<(&str, &str)> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap().trim())
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .collect()
+}
+
+pub struct TuningConsole {
+    pub open: bool,
+    status: String,
+}
+
+impl TuningConsole {
+    pub fn new() -> TuningConsole {
+        TuningConsole {
+            open: false,
+            status: String::new(),
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    pub fn draw(&mut self, tuning: &mut Tuning) {
+        if !self.open {
+            return;
+        }
+
+        let status = &mut self.status;
+        root_ui().window(hash!(), vec2(10.0, 10.0), vec2(420.0, 360.0), |ui| {
+            for (name, field, range) in tuning.fields_mut() {
+                ui.slider(hash!(name), name, range, field);
+            }
+            ui.separator();
+            if ui.button(None, "export") {
+                *status = match tuning.export(TUNING_PATH) {
+                    Ok(()) => format!("saved to {}", TUNING_PATH),
+                    Err(err) => format!("export failed: {}", err),
+                };
+            }
+            ui.same_line(0.0);
+            if ui.button(None, "reset") {
+                *tuning = Tuning::default();
+            }
+            ui.label(None, status);
+        });
+    }
+}
@@ -0,0 +1,110 @@
+use crate::nalgebra::Vector2;
+use crate::*;
+
+// TYRES:
+// slip based grip. after every step the contacts of a wheel are read from the narrow phase, the
+// longitudinal slip ratio between the wheel surface and the ground is worked out and the wheel
+// friction for the next step follows a grip curve: full grip up to the peak slip, then it falls
+// off towards the sliding grip. the traction limit is the friction times the wheel load, a wheel
+// driven or braked past it breaks loose and only has the sliding grip left, so it spins up (or
+// locks) instead of rapier's friction holding it right at the limit.
+const PEAK_SLIP: f32 = 0.15;
+const FULL_SLIDE_SLIP: f32 = 1.0;
+const SLIDE_GRIP: f32 = 0.35; // share of the peak friction left when sliding
+const MIN_SLIP_SPEED: f32 = 1.0; // in m/s, keeps the slip ratio finite when standing still
+const SPINNING_SLIP: f32 = 0.5;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct TyreState {
+    pub slip_ratio: f32, // positive when the wheel spins faster than the ground moves under it
+    pub load: f32,       // in N, normal force of the last step
+    pub overpowered: bool, // wheel torque of the last step past the traction limit
+}
+
+impl TyreState {
+    pub fn is_spinning(&self) -> bool {
+        self.overpowered || self.slip_ratio.abs() > SPINNING_SLIP
+    }
+
+    // most wheel torque the ground takes before the tyre breaks loose, in N m
+    pub fn traction_limit(&self, friction: f32, wheel_radius: f32) -> f32 {
+        friction * grip(self.slip_ratio) * self.load * wheel_radius
+    }
+
+    // share of the peak friction for the next step
+    pub fn grip(&self) -> f32 {
+        if self.overpowered {
+            SLIDE_GRIP
+        } else {
+            grip(self.slip_ratio)
+        }
+    }
+}
+
+// share of the peak friction at a given slip ratio
+pub fn grip(slip_ratio: f32) -> f32 {
+    let slip = slip_ratio.abs();
+    if slip <= PEAK_SLIP {
+        1.0
+    } else if slip >= FULL_SLIDE_SLIP {
+        SLIDE_GRIP
+    } else {
+        1.0 - (1.0 - SLIDE_GRIP) * (slip - PEAK_SLIP) / (FULL_SLIDE_SLIP - PEAK_SLIP)
+    }
+}
+
+// slip and load of a wheel from its active contacts, None when it touches nothing
+pub fn tyre_state(
+    wheel_collider: ColliderHandle,
+    wheel_radius: f32,
+    dt: f32,
+    body_set: &RigidBodySet,
+    coll_set: &ColliderSet,
+    narrow_phase: &NarrowPhase,
+) -> Option<TyreState> {
+    let wheel_body = &body_set[coll_set[wheel_collider].parent()?];
+    let center = *wheel_body.translation();
+
+    let mut impulse = 0.0;
+    let mut contact = None;
+    for pair in narrow_phase.contacts_with(wheel_collider) {
+        if !pair.has_any_active_contact {
+            continue;
+        }
+        let other = if pair.collider1 == wheel_collider {
+            pair.collider2
+        } else {
+            pair.collider1
+        };
+        for manifold in pair.manifolds.iter() {
+            impulse += manifold
+                .points
+                .iter()
+                .map(|point| point.data.impulse)
+                .sum::<f32>();
+            if let Some(solver_contact) = manifold.data.solver_contacts.first() {
+                contact.get_or_insert((solver_contact.point.coords, other));
+            }
+        }
+    }
+    let (point, other) = contact?;
+
+    // ground tangent, and the speed of the ground surface under the wheel:
+    let normal = (center - point)
+        .try_normalize(f32::EPSILON)
+        .unwrap_or_else(|| vector![0.0, -1.0]);
+    let tangent = vector![-normal.y, normal.x];
+    let ground_velocity = coll_set[other].parent().map_or(Vector2::zeros(), |ground| {
+        body_set[ground].velocity_at_point(&Point::from(point))
+    });
+
+    let ground_speed = (wheel_body.linvel() - ground_velocity).dot(&tangent);
+    let radius = -normal * wheel_radius;
+    let rolling_speed = -(vector![-radius.y, radius.x] * wheel_body.angvel()).dot(&tangent);
+
+    Some(TyreState {
+        slip_ratio: (rolling_speed - ground_speed) / ground_speed.abs().max(MIN_SLIP_SPEED),
+        load: impulse / dt.max(f32::EPSILON),
+        overpowered: false,
+    })
+}
@@ -6,20 +6,85 @@ use crate::*;
 //     to_range.0 + (source - from_range.0) * (to_range.1 - to_range.0) / (from_range.1 - from_range.0)
 // }
 
-pub fn turn_around(input: &Unit<Complex<f32>>) -> Unit<Complex<f32>> {
-    let output = input.angle() + PI;
-    UnitComplex::new(output)
+// VIEWPORT:
+// largest rect of ASPECT_RATIO that fits the window, centered. everything outside is letterboxed.
+pub struct Viewport {
+    pub x: f32,
+    pub y: f32,
+    pub w: f32,
+    pub h: f32,
+}
+
+pub fn viewport() -> Viewport {
+    let (screen_w, screen_h) = (screen_width(), screen_height());
+    let (w, h) = if screen_w / screen_h > ASPECT_RATIO {
+        (screen_h * ASPECT_RATIO, screen_h)
+    } else {
+        (screen_w, screen_w / ASPECT_RATIO)
+    };
+    Viewport {
+        x: (screen_w - w) / 2.0,
+        y: (screen_h - h) / 2.0,
+        w,
+        h,
+    }
+}
+
+pub fn pixels_per_metre() -> f32 {
+    viewport().w / ARENA_WIDTH
+}
+
+pub fn draw_letterbox(color: Color) {
+    let view = viewport();
+    let (screen_w, screen_h) = (screen_width(), screen_height());
+    draw_rectangle(0.0, 0.0, view.x, screen_h, color);
+    draw_rectangle(
+        view.x + view.w,
+        0.0,
+        screen_w - view.x - view.w,
+        screen_h,
+        color,
+    );
+    draw_rectangle(0.0, 0.0, screen_w, view.y, color);
+    draw_rectangle(
+        0.0,
+        view.y + view.h,
+        screen_w,
+        screen_h - view.y - view.h,
+        color,
+    );
+}
+
+// CMD+Q on macos
+pub fn quit_shortcut_pressed() -> bool {
+    std::env::consts::OS == "macos" && is_key_down(KeyCode::LeftSuper) && is_key_down(KeyCode::Q)
+}
+
+// miniquad only switches at runtime on windows and the web, elsewhere the window keeps the
+// Conf.fullscreen it was opened with
+pub const FULLSCREEN_SWITCHABLE: bool = !cfg!(any(
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "android"
+));
+
+pub fn set_fullscreen(fullscreen: bool) {
+    unsafe {
+        get_internal_gl().quad_context.set_fullscreen(fullscreen);
+    }
 }
 
 pub fn size_mtr_to_pxl(metres: f32) -> f32 {
-    metres * PIXELS_PER_METRE
+    metres * pixels_per_metre()
 }
 pub fn pos_x_mtr_to_pxl(pos_x: f32) -> f32 {
-    pos_x * PIXELS_PER_METRE
+    viewport().x + pos_x * pixels_per_metre()
 }
 pub fn pos_y_mtr_to_pxl(pos_y: f32) -> f32 {
-    let ceiling = screen_height() / 2.0 - size_mtr_to_pxl(ARENA_HEIGHT) / 2.0;
-    ceiling + (pos_y * PIXELS_PER_METRE)
+    let view = viewport();
+    let ceiling = view.y + view.h / 2.0 - size_mtr_to_pxl(ARENA_HEIGHT) / 2.0;
+    ceiling + size_mtr_to_pxl(pos_y)
 }
 pub fn pos_vec_mtr_to_pxl(position: Vector2<f32>) -> Vector2<f32> {
     let x = pos_x_mtr_to_pxl(position.x);
@@ -27,6 +92,7 @@ pub fn pos_vec_mtr_to_pxl(position: Vector2<f32>) -> Vector2<f32> {
     vector![x, y]
 }
 
+#[allow(dead_code)] // the car draws its hitbox outline itself since the compound hitboxes
 pub fn draw_line_center(
     center: Vector2<f32>,
     rotation: f32,